@@ -0,0 +1,84 @@
+// Bounded concurrency and a short-TTL read cache in front of the unified
+// backend, cutting the interactive-UI latency and repeated-login cost
+// `folder_info`, `resync_folder`, `test_server_connection`, and
+// `check_database_status` were paying on every call.
+//
+// The actual NNTP sockets and Postgres connections are owned by the
+// persistent backend process (`unified_backend::BackendSupervisor`, which
+// already stays alive across calls rather than being re-spawned per
+// command); there is no Rust-side NNTP or Postgres client in this tree for
+// a bb8 `ManageConnection` to wrap. What Rust can usefully own instead is
+// (a) capping how many calls are in flight at once, so the UI can't pile
+// up concurrent AUTHINFO handshakes past what the server's thread limit
+// allows, and (b) a short-TTL cache so rapid repeated polls of the same
+// read (the UI re-checking `folder_info` on a timer, say) reuse the last
+// answer instead of re-paying the round trip every time.
+
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Semaphore;
+
+use crate::unified_backend::UnifiedResponse;
+
+/// How long a cached read stays valid before the next call re-queries the
+/// backend.
+const CACHE_TTL: Duration = Duration::from_secs(5);
+
+struct CachedResponse {
+    response: UnifiedResponse,
+    cached_at: Instant,
+}
+
+/// Bounded-concurrency, short-TTL-cached front end for
+/// [`crate::execute_unified_command`]. One instance lives in `AppState`.
+pub struct BackendPool {
+    in_flight: Semaphore,
+    cache: StdMutex<HashMap<String, CachedResponse>>,
+}
+
+impl BackendPool {
+    /// `max_connections` caps how many calls through this pool may be in
+    /// flight at once, mirroring the news server's own connection limit.
+    pub fn new(max_connections: usize) -> Self {
+        Self {
+            in_flight: Semaphore::new(max_connections.max(1)),
+            cache: StdMutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `command` with `args` through the pool: served from cache if a
+    /// fresh-enough answer exists, otherwise dispatched to the backend
+    /// (bounded by `max_connections` in-flight calls) and cached for reuse.
+    /// Only safe for idempotent reads -- mutating commands should use
+    /// [`BackendPool::call`] instead.
+    pub async fn call_cached(&self, command: &str, args: serde_json::Value) -> Result<UnifiedResponse, String> {
+        let cache_key = cache_key(command, &args);
+        if let Some(cached) = self.cache.lock().unwrap().get(&cache_key) {
+            if cached.cached_at.elapsed() < CACHE_TTL {
+                return Ok(cached.response.clone());
+            }
+        }
+
+        let response = self.call(command, args).await?;
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(cache_key, CachedResponse { response: response.clone(), cached_at: Instant::now() });
+
+        Ok(response)
+    }
+
+    /// Run `command` through the pool, bounded by `max_connections`
+    /// in-flight calls, without caching the result.
+    pub async fn call(&self, command: &str, args: serde_json::Value) -> Result<UnifiedResponse, String> {
+        let _permit = self.in_flight.acquire().await.map_err(|e| e.to_string())?;
+        crate::execute_unified_command(command, args).await
+    }
+}
+
+fn cache_key(command: &str, args: &serde_json::Value) -> String {
+    format!("{}:{}", command, args)
+}
@@ -1,38 +1,28 @@
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use std::collections::HashMap;
 use std::fs;
-use std::process::Command as ProcessCommand;
-use std::path::PathBuf;
+use tauri::Emitter;
 
-// Helper function to get the correct Python command for the OS
-fn get_python_command() -> &'static str {
-    if cfg!(target_os = "windows") {
-        "python"
-    } else {
-        "python3"
-    }
-}
+use crate::unified_backend::execute_unified_command;
 
-// Helper function to get the workspace directory
-fn get_workspace_dir() -> PathBuf {
-    std::env::current_dir()
-        .ok()
-        .and_then(|p| {
-            // Try to find the workspace root by looking for src/cli.py
-            let mut current = p.as_path();
-            loop {
-                if current.join("src").join("cli.py").exists() {
-                    return Some(current.to_path_buf());
-                }
-                match current.parent() {
-                    Some(parent) => current = parent,
-                    None => return None,
-                }
-            }
-        })
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
+/// Monotonic counters surfaced on the Prometheus `/metrics` endpoint (see
+/// `metrics.rs`). Cheap to clone into the metrics server since every field
+/// is an `Arc`; incrementing one here is a single relaxed atomic add.
+#[derive(Clone, Default)]
+pub struct MetricsHandle {
+    pub uploads_queued: Arc<AtomicU64>,
+    pub downloads_started: Arc<AtomicU64>,
+    pub shares_created: Arc<AtomicU64>,
+    pub bytes_transferred: Arc<AtomicU64>,
+    /// Cumulative bytes uploaded, diffed between ticks by
+    /// `stats_sampler::watch` to get a live upload rate.
+    pub bytes_uploaded: Arc<AtomicU64>,
+    /// Cumulative bytes downloaded, diffed between ticks by
+    /// `stats_sampler::watch` to get a live download rate.
+    pub bytes_downloaded: Arc<AtomicU64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +69,18 @@ pub struct SystemState {
     bandwidth_limits: Arc<Mutex<BandwidthLimits>>,
     #[allow(dead_code)]
     statistics: Arc<Mutex<HashMap<String, f64>>>,
+    /// Protocol version negotiated with the backend daemon during its last
+    /// handshake; `None` until the daemon has been contacted at least once.
+    protocol_version: Arc<Mutex<Option<u32>>>,
+    /// Full version/capability handshake result, refreshed alongside
+    /// `protocol_version`.
+    backend_version: Arc<Mutex<Option<crate::unified_backend::BackendVersion>>>,
+    /// The task tailing the backend's event stream while it's running, and
+    /// the source filter it's currently applying. `None` task means
+    /// streaming is stopped.
+    log_stream_task: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    log_stream_filter: Arc<Mutex<Option<String>>>,
+    metrics: MetricsHandle,
 }
 
 impl SystemState {
@@ -121,9 +123,60 @@ impl SystemState {
                 enabled: false,
             })),
             statistics: Arc::new(Mutex::new(HashMap::new())),
+            protocol_version: Arc::new(Mutex::new(None)),
+            backend_version: Arc::new(Mutex::new(None)),
+            log_stream_task: Arc::new(Mutex::new(None)),
+            log_stream_filter: Arc::new(Mutex::new(None)),
+            metrics: MetricsHandle::default(),
         }
     }
-    
+
+    /// A cloneable handle to this state's metrics counters, for handing to
+    /// the standalone metrics HTTP server.
+    pub fn metrics_handle(&self) -> MetricsHandle {
+        self.metrics.clone()
+    }
+
+    pub fn record_upload_queued(&self) {
+        self.metrics.uploads_queued.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_download_started(&self) {
+        self.metrics.downloads_started.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_share_created(&self) {
+        self.metrics.shares_created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_bytes_transferred(&self, bytes: u64) {
+        self.metrics.bytes_transferred.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn record_bytes_uploaded(&self, bytes: u64) {
+        self.metrics.bytes_transferred.fetch_add(bytes, Ordering::Relaxed);
+        self.metrics.bytes_uploaded.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn record_bytes_downloaded(&self, bytes: u64) {
+        self.metrics.bytes_transferred.fetch_add(bytes, Ordering::Relaxed);
+        self.metrics.bytes_downloaded.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Refresh the locally cached protocol version from whatever the
+    /// backend daemon most recently negotiated.
+    pub async fn sync_protocol_version(&self) {
+        let negotiated = crate::unified_backend::negotiated_version().await;
+        *self.protocol_version.lock().await = negotiated;
+    }
+
+    /// Refresh the locally cached backend version/capability summary from
+    /// whatever the backend daemon most recently negotiated.
+    pub async fn sync_backend_version(&self) {
+        let version = crate::unified_backend::backend_version().await;
+        *self.backend_version.lock().await = version;
+    }
+
     pub async fn add_log(&self, level: String, message: String, source: Option<String>) {
         let mut logs = self.logs.lock().await;
         let entry = LogEntry {
@@ -167,33 +220,21 @@ pub async fn get_logs(
     filter: Option<serde_json::Value>,
     state: tauri::State<'_, SystemState>,
 ) -> Result<Vec<LogEntry>, String> {
-    // First try to get logs from Python backend
-    let output = ProcessCommand::new(get_python_command())
-        .args(&[
-            "-c",
-            "from src.cli import UsenetSyncCLI; \
-             cli = UsenetSyncCLI(); \
-             import json; \
-             logs = cli.integrated_backend.log_manager.get_logs(); \
-             print(json.dumps([log.to_dict() for log in logs]))"
-        ])
-        .current_dir(&get_workspace_dir())
-        .output();
-    
-    if let Ok(output) = output {
-        if output.status.success() {
-            if let Ok(backend_logs) = serde_json::from_slice::<Vec<LogEntry>>(&output.stdout) {
-                // Merge with local logs
-                let mut logs = state.logs.lock().await;
-                for log in backend_logs {
-                    if !logs.iter().any(|l| l.timestamp == log.timestamp && l.message == log.message) {
-                        logs.push(log);
-                    }
+    // Pull any logs the backend daemon has buffered since our last call.
+    if let Ok(response) = execute_unified_command("get_logs", serde_json::json!({})).await {
+        if let Some(backend_logs) = response
+            .data
+            .and_then(|d| serde_json::from_value::<Vec<LogEntry>>(d).ok())
+        {
+            let mut logs = state.logs.lock().await;
+            for log in backend_logs {
+                if !logs.iter().any(|l| l.timestamp == log.timestamp && l.message == log.message) {
+                    logs.push(log);
                 }
             }
         }
     }
-    
+
     let logs = state.logs.lock().await;
     
     // Apply filters if provided
@@ -230,6 +271,79 @@ pub async fn get_logs(
     }
 }
 
+/// Start tailing the backend's event stream: log lines are appended to the
+/// ring buffer and re-emitted as `log-line`, and transfer progress is
+/// re-emitted as `transfer-progress`, as they happen instead of waiting for
+/// the next `get_logs` poll. Safe to call again to change the source
+/// filter without restarting the stream.
+#[tauri::command]
+pub async fn start_log_stream(
+    source_filter: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SystemState>,
+) -> Result<(), String> {
+    *state.log_stream_filter.lock().await = source_filter;
+
+    let mut task = state.log_stream_task.lock().await;
+    if task.is_some() {
+        return Ok(());
+    }
+
+    let logs = state.logs.clone();
+    let filter = state.log_stream_filter.clone();
+    let mut events = crate::unified_backend::subscribe_events();
+
+    *task = Some(tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            };
+
+            match event.event.as_str() {
+                "log_line" => {
+                    let entry = match serde_json::from_value::<LogEntry>(event.data) {
+                        Ok(entry) => entry,
+                        Err(_) => continue,
+                    };
+                    if let Some(wanted) = filter.lock().await.as_deref() {
+                        if entry.source.as_deref() != Some(wanted) {
+                            continue;
+                        }
+                    }
+
+                    {
+                        let mut logs = logs.lock().await;
+                        logs.push(entry.clone());
+                        if logs.len() > 10000 {
+                            let drain_count = logs.len() - 10000;
+                            logs.drain(0..drain_count);
+                        }
+                    }
+
+                    let _ = app.emit("log-line", &entry);
+                }
+                "transfer_progress" => {
+                    let _ = app.emit("transfer-progress", &event.data);
+                }
+                _ => {}
+            }
+        }
+    }));
+
+    Ok(())
+}
+
+/// Stop tailing the backend's event stream started by [`start_log_stream`].
+#[tauri::command]
+pub async fn stop_log_stream(state: tauri::State<'_, SystemState>) -> Result<(), String> {
+    if let Some(task) = state.log_stream_task.lock().await.take() {
+        task.abort();
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn set_bandwidth_limit(
     upload_kbps: u32,
@@ -237,26 +351,16 @@ pub async fn set_bandwidth_limit(
     enabled: bool,
     state: tauri::State<'_, SystemState>,
 ) -> Result<(), String> {
-    // Apply to Python backend
-    let output = ProcessCommand::new(get_python_command())
-        .args(&[
-            "-c",
-            &format!(
-                "from src.cli import UsenetSyncCLI; \
-                 cli = UsenetSyncCLI(); \
-                 cli.integrated_backend.set_bandwidth_limits({}, {})",
-                if enabled { upload_kbps * 1024 } else { 0 },
-                if enabled { download_kbps * 1024 } else { 0 }
-            )
-        ])
-        .current_dir(&get_workspace_dir())
-        .output()
-        .map_err(|e| e.to_string())?;
-    
-    if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    // Apply to the backend daemon
+    let args = serde_json::json!({
+        "upload_bps": if enabled { upload_kbps * 1024 } else { 0 },
+        "download_bps": if enabled { download_kbps * 1024 } else { 0 },
+    });
+    let response = execute_unified_command("set_bandwidth_limits", args).await?;
+    if !response.success {
+        return Err(response.error.unwrap_or_else(|| "Failed to set bandwidth limits".to_string()));
     }
-    
+
     let mut limits = state.bandwidth_limits.lock().await;
     *limits = BandwidthLimits {
         upload_kbps,
@@ -307,33 +411,13 @@ pub async fn get_statistics(_state: tauri::State<'_, SystemState>) -> Result<Sys
         0.0
     };
     
-    // Get network stats from Python backend
-    let network_speed = if let Ok(output) = ProcessCommand::new(get_python_command())
-        .args(&[
-            "-c",
-            "from src.cli import UsenetSyncCLI; \
-             cli = UsenetSyncCLI(); \
-             stats = cli.integrated_backend.get_bandwidth_stats(); \
-             import json; \
-             print(json.dumps({\
-                 'upload': stats['upload']['current_speed'], \
-                 'download': stats['download']['current_speed']\
-             }))"
-        ])
-        .current_dir(&get_workspace_dir())
-        .output() 
-    {
-        if output.status.success() {
-            if let Ok(speeds) = serde_json::from_slice::<NetworkSpeed>(&output.stdout) {
-                speeds
-            } else {
-                NetworkSpeed { upload: 0, download: 0 }
-            }
-        } else {
-            NetworkSpeed { upload: 0, download: 0 }
-        }
-    } else {
-        NetworkSpeed { upload: 0, download: 0 }
+    // Get network stats from the backend daemon
+    let network_speed = match execute_unified_command("get_bandwidth_stats", serde_json::json!({})).await {
+        Ok(response) if response.success => response
+            .data
+            .and_then(|d| serde_json::from_value::<NetworkSpeed>(d).ok())
+            .unwrap_or(NetworkSpeed { upload: 0, download: 0 }),
+        _ => NetworkSpeed { upload: 0, download: 0 },
     };
     
     Ok(SystemStats {
@@ -347,57 +431,34 @@ pub async fn get_statistics(_state: tauri::State<'_, SystemState>) -> Result<Sys
 #[tauri::command]
 pub async fn export_data(options: serde_json::Value, _state: tauri::State<'_, SystemState>) -> Result<String, String> {
     
-    // Call Python backend for full export
-    let output = ProcessCommand::new(get_python_command())
-        .args(&[
-            "-c",
-            &format!(
-                "from src.cli import UsenetSyncCLI; \
-                 cli = UsenetSyncCLI(); \
-                 import json; \
-                 data = cli.integrated_backend.export_settings(\
-                     password='{}' if {} else None\
-                 ); \
-                 print(data)",
-                options.get("password").and_then(|v| v.as_str()).unwrap_or(""),
-                options.get("encrypt").and_then(|v| v.as_bool()).unwrap_or(false)
-            )
-        ])
-        .current_dir(&get_workspace_dir())
-        .output()
-        .map_err(|e| e.to_string())?;
-    
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    // Call the backend daemon for full export
+    let args = serde_json::json!({
+        "password": options.get("password").and_then(|v| v.as_str()),
+        "encrypt": options.get("encrypt").and_then(|v| v.as_bool()).unwrap_or(false),
+    });
+    let response = execute_unified_command("export_settings", args).await?;
+    if !response.success {
+        return Err(response.error.unwrap_or_else(|| "Export failed".to_string()));
+    }
+
+    match response.data {
+        Some(serde_json::Value::String(s)) => Ok(s),
+        Some(other) => Ok(other.to_string()),
+        None => Ok(String::new()),
     }
 }
 
 #[tauri::command]
 pub async fn import_data(data: String, options: serde_json::Value, state: tauri::State<'_, SystemState>) -> Result<bool, String> {
-    // Call Python backend for import
-    let output = ProcessCommand::new(get_python_command())
-        .args(&[
-            "-c",
-            &format!(
-                "from src.cli import UsenetSyncCLI; \
-                 cli = UsenetSyncCLI(); \
-                 result = cli.integrated_backend.import_settings(\
-                     '{}', \
-                     password='{}' if {} else None\
-                 ); \
-                 print('success' if result else 'failed')",
-                data,
-                options.get("password").and_then(|v| v.as_str()).unwrap_or(""),
-                options.get("encrypted").and_then(|v| v.as_bool()).unwrap_or(false)
-            )
-        ])
-        .current_dir(&get_workspace_dir())
-        .output()
-        .map_err(|e| e.to_string())?;
-    
-    if output.status.success() && String::from_utf8_lossy(&output.stdout).contains("success") {
+    // Call the backend daemon for import
+    let args = serde_json::json!({
+        "data": data,
+        "password": options.get("password").and_then(|v| v.as_str()),
+        "encrypted": options.get("encrypted").and_then(|v| v.as_bool()).unwrap_or(false),
+    });
+    let response = execute_unified_command("import_settings", args).await?;
+
+    if response.success {
         state.add_log(
             "INFO".to_string(),
             "Data imported successfully".to_string(),
@@ -405,7 +466,7 @@ pub async fn import_data(data: String, options: serde_json::Value, state: tauri:
         ).await;
         Ok(true)
     } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
+        Err(response.error.unwrap_or_else(|| "Import failed".to_string()))
     }
 }
 
@@ -426,23 +487,12 @@ pub async fn clear_cache(state: tauri::State<'_, SystemState>) -> Result<(), Str
         }
     }
     
-    // Clear Python backend cache
-    let output = ProcessCommand::new(get_python_command())
-        .args(&[
-            "-c",
-            "from src.cli import UsenetSyncCLI; \
-             cli = UsenetSyncCLI(); \
-             cli.integrated_backend.data_manager.clear_cache(); \
-             cli.integrated_backend.cleanup_old_data(days=0)"
-        ])
-        .current_dir(&get_workspace_dir())
-        .output()
-        .map_err(|e| e.to_string())?;
-    
-    if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    // Clear the backend daemon's cache
+    let response = execute_unified_command("clear_cache", serde_json::json!({})).await?;
+    if !response.success {
+        return Err(response.error.unwrap_or_else(|| "Failed to clear backend cache".to_string()));
     }
-    
+
     state.add_log(
         "INFO".to_string(),
         "Cache cleared successfully".to_string(),
@@ -469,50 +519,35 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
 
 #[tauri::command]
 pub async fn restart_services(state: tauri::State<'_, SystemState>) -> Result<(), String> {
-    // Stop existing Python backend service
-    #[cfg(not(target_os = "windows"))]
-    {
-        ProcessCommand::new("pkill")
-            .args(&["-f", "usenet_sync"])
-            .output()
-            .ok();
-        
-        ProcessCommand::new("pkill")
-            .args(&["-f", "cli.py"])
-            .output()
-            .ok();
-        
-        std::thread::sleep(std::time::Duration::from_secs(2));
-        
-        // Start Python backend service
-        ProcessCommand::new(get_python_command())
-            .args(&["src/cli.py", "--daemon"])
-            .current_dir(&get_workspace_dir())
-            .spawn()
-            .map_err(|e| format!("Failed to start service: {}", e))?;
-    }
-    
-    #[cfg(target_os = "windows")]
-    {
-        ProcessCommand::new("taskkill")
-            .args(&["/F", "/IM", "python.exe", "/FI", "WINDOWTITLE eq UsenetSync*"])
-            .output()
-            .ok();
-        
-        std::thread::sleep(std::time::Duration::from_secs(2));
-        
-        ProcessCommand::new(get_python_command())
-            .args(&["src\\cli.py", "--daemon"])
-            .current_dir(&get_workspace_dir())
-            .spawn()
-            .map_err(|e| format!("Failed to start service: {}", e))?;
-    }
-    
+    // Kill the supervised backend daemon and respawn it, confirming the
+    // replacement actually answers before reporting success.
+    crate::unified_backend::restart_backend().await?;
+    state.sync_protocol_version().await;
+    state.sync_backend_version().await;
+
     state.add_log(
         "INFO".to_string(),
         "Services restarted successfully".to_string(),
         Some("system".to_string())
     ).await;
-    
+
     Ok(())
 }
+
+#[tauri::command]
+pub async fn get_protocol_version(state: tauri::State<'_, SystemState>) -> Result<Option<u32>, String> {
+    state.sync_protocol_version().await;
+    Ok(*state.protocol_version.lock().await)
+}
+
+/// Report the negotiated backend's version and capabilities, so the UI can
+/// gracefully gate functionality when the bundled and system Python
+/// backends diverge, instead of discovering a gap only once a command
+/// fails.
+#[tauri::command]
+pub async fn get_backend_version(
+    state: tauri::State<'_, SystemState>,
+) -> Result<Option<crate::unified_backend::BackendVersion>, String> {
+    state.sync_backend_version().await;
+    Ok(state.backend_version.lock().await.clone())
+}
@@ -0,0 +1,162 @@
+// Encrypted-at-rest credential storage for server passwords.
+//
+// `save_server_config` used to serialize the whole `ServerConfig` --
+// including the NNTP password -- as pretty-printed plaintext JSON under
+// the config dir. A master passphrase (set once via
+// `set_master_passphrase`, supplied again via `unlock` on a later launch)
+// is put through Argon2id to derive a 256-bit key that's never written to
+// disk; the password field alone is encrypted with XChaCha20-Poly1305
+// under that key before the config file is written. Non-secret fields
+// (hostname/port/ssl/...) stay plaintext so the UI can display them
+// without unlocking anything. The derived key lives only in `AppState` for
+// the lifetime of the unlocked session.
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use argon2::Argon2;
+
+fn config_dir() -> PathBuf {
+    dirs::config_dir().unwrap_or_else(std::env::temp_dir).join("usenet-sync")
+}
+
+fn config_path() -> PathBuf {
+    config_dir().join("server.json")
+}
+
+fn salt_path() -> PathBuf {
+    config_dir().join("server_key_salt.json")
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeySalt {
+    salt: String,
+}
+
+/// A server config with its password encrypted at rest. Non-secret fields
+/// stay plaintext.
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedServerConfig {
+    hostname: String,
+    port: u16,
+    username: String,
+    #[serde(rename = "useSsl")]
+    use_ssl: bool,
+    #[serde(rename = "maxConnections")]
+    max_connections: u32,
+    group: String,
+    /// Base64 XChaCha20-Poly1305 ciphertext of the password, under the key
+    /// derived from the master passphrase.
+    #[serde(rename = "encryptedPassword")]
+    encrypted_password: String,
+    /// Base64 24-byte nonce used for `encrypted_password`.
+    nonce: String,
+}
+
+fn load_or_create_salt() -> Result<[u8; 16], String> {
+    let path = salt_path();
+    if let Ok(contents) = fs::read_to_string(&path) {
+        if let Ok(stored) = serde_json::from_str::<KeySalt>(&contents) {
+            if let Ok(bytes) = base64::decode(&stored.salt) {
+                if bytes.len() == 16 {
+                    let mut salt = [0u8; 16];
+                    salt.copy_from_slice(&bytes);
+                    return Ok(salt);
+                }
+            }
+        }
+    }
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    fs::create_dir_all(config_dir()).map_err(|e| e.to_string())?;
+    let stored = KeySalt { salt: base64::encode(salt) };
+    fs::write(&path, serde_json::to_string_pretty(&stored).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    Ok(salt)
+}
+
+/// Derive a 256-bit key from `passphrase` via Argon2id, generating and
+/// persisting a new random salt the first time a master passphrase is set
+/// on this install.
+fn derive_key(passphrase: &str) -> Result<[u8; 32], String> {
+    let salt = load_or_create_salt()?;
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// Derive a key from `passphrase` and, if a config is already on disk,
+/// confirm it actually decrypts before handing the key back -- so a typo'd
+/// passphrase fails loudly at `unlock` instead of silently producing a key
+/// that can't read anything.
+pub fn derive_and_verify(passphrase: &str) -> Result<[u8; 32], String> {
+    let key = derive_key(passphrase)?;
+    if config_path().exists() {
+        load(&key)?;
+    }
+    Ok(key)
+}
+
+fn encrypt_password(key: &[u8; 32], password: &str) -> Result<(String, String), String> {
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+    let mut nonce_bytes = [0u8; 24];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce_bytes), password.as_bytes())
+        .map_err(|e| e.to_string())?;
+    Ok((base64::encode(ciphertext), base64::encode(nonce_bytes)))
+}
+
+fn decrypt_password(key: &[u8; 32], encrypted_password: &str, nonce: &str) -> Result<String, String> {
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce_bytes = base64::decode(nonce).map_err(|e| e.to_string())?;
+    let ciphertext = base64::decode(encrypted_password).map_err(|e| e.to_string())?;
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| "failed to decrypt password (wrong master passphrase?)".to_string())?;
+    String::from_utf8(plaintext).map_err(|e| e.to_string())
+}
+
+/// Encrypt `config`'s password under `key` and persist the result,
+/// overwriting any previously saved config.
+pub fn save(config: &crate::ServerConfig, key: &[u8; 32]) -> Result<(), String> {
+    let (encrypted_password, nonce) = encrypt_password(key, &config.password)?;
+    let encrypted = EncryptedServerConfig {
+        hostname: config.hostname.clone(),
+        port: config.port,
+        username: config.username.clone(),
+        use_ssl: config.use_ssl,
+        max_connections: config.max_connections,
+        group: config.group.clone(),
+        encrypted_password,
+        nonce,
+    };
+
+    fs::create_dir_all(config_dir()).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&encrypted).map_err(|e| e.to_string())?;
+    fs::write(config_path(), json).map_err(|e| e.to_string())
+}
+
+/// Load the persisted config and decrypt its password with `key`.
+pub fn load(key: &[u8; 32]) -> Result<crate::ServerConfig, String> {
+    let contents = fs::read_to_string(config_path()).map_err(|e| e.to_string())?;
+    let encrypted: EncryptedServerConfig = serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+    let password = decrypt_password(key, &encrypted.encrypted_password, &encrypted.nonce)?;
+
+    Ok(crate::ServerConfig {
+        hostname: encrypted.hostname,
+        port: encrypted.port,
+        username: encrypted.username,
+        password,
+        use_ssl: encrypted.use_ssl,
+        max_connections: encrypted.max_connections,
+        group: encrypted.group,
+    })
+}
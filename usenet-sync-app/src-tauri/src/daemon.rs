@@ -0,0 +1,234 @@
+// Headless daemon mode.
+//
+// `--headless --listen ADDR` starts a small newline-delimited JSON
+// request/response server instead of the Tauri GUI, routing incoming
+// `{token, command, args}` messages through the same
+// `execute_unified_command_licensed` plumbing the GUI commands use, and
+// forwarding backend progress events back to every connected client as
+// they arrive. A bearer token read from `USENETSYNC_DAEMON_TOKEN` gates
+// every request so the local port isn't open to anything else on the box;
+// if the variable isn't set, no token is required (matches running the GUI
+// unauthenticated on localhost). As an alternative to the shared bearer
+// token, a caller can instead authenticate with a signed
+// `identity::IdentityProof` from a key listed in
+// `USENETSYNC_DAEMON_TRUSTED_KEYS` -- useful for a remote client that
+// holds its own device identity rather than a copy of the daemon's token.
+//
+// This lets a thin CLI or cron job drive indexing, uploading, and
+// publishing without the desktop window -- the GUI's `windows_subsystem =
+// "windows"` attribute is a compile-time flag the OS reads at process
+// creation, so it can't be toggled per-invocation; headless mode simply
+// never creates a Tauri window, which is what matters for automation.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{execute_unified_command_licensed, AppState};
+
+/// Parsed `--headless --listen ADDR` invocation.
+pub struct HeadlessConfig {
+    pub listen_addr: String,
+}
+
+/// Scan the process's CLI args for `--headless`. Returns `None` (falling
+/// through to the normal GUI path) when it isn't present; otherwise reads
+/// `--listen ADDR`, defaulting to `127.0.0.1:9899`.
+pub fn parse_args() -> Option<HeadlessConfig> {
+    let args: Vec<String> = std::env::args().collect();
+    if !args.iter().any(|a| a == "--headless") {
+        return None;
+    }
+
+    let listen_addr = args
+        .iter()
+        .position(|a| a == "--listen")
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+        .unwrap_or_else(|| "127.0.0.1:9899".to_string());
+
+    Some(HeadlessConfig { listen_addr })
+}
+
+#[derive(Debug, Deserialize)]
+struct DaemonRequest {
+    #[serde(default)]
+    token: String,
+    /// Alternative to `token`: a signed proof from a key in
+    /// `USENETSYNC_DAEMON_TRUSTED_KEYS`, paired with `identity_proof`.
+    #[serde(default)]
+    identity_public_key: Option<String>,
+    #[serde(default)]
+    identity_proof: Option<crate::identity::IdentityProof>,
+    command: String,
+    #[serde(default)]
+    args: serde_json::Value,
+}
+
+/// Parse `USENETSYNC_DAEMON_TRUSTED_KEYS` (comma-separated base64 Ed25519
+/// public keys) once per connection. Empty/unset means no identity is
+/// trusted, so a connection can only authenticate with the bearer token.
+fn trusted_identity_keys() -> Vec<Vec<u8>> {
+    std::env::var("USENETSYNC_DAEMON_TRUSTED_KEYS")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .filter_map(|key| base64::decode(key.trim()).ok())
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct DaemonResponse {
+    success: bool,
+    data: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+/// Bind `config.listen_addr` and serve requests until the process exits.
+pub async fn run(config: HeadlessConfig, state: Arc<AppState>) {
+    let listener = match TcpListener::bind(&config.listen_addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("daemon: failed to bind {}: {}", config.listen_addr, e);
+            return;
+        }
+    };
+
+    println!("daemon: listening on {}", config.listen_addr);
+
+    loop {
+        let (socket, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(_) => continue,
+        };
+        let state = state.clone();
+        tokio::spawn(handle_connection(socket, state));
+    }
+}
+
+async fn handle_connection(socket: tokio::net::TcpStream, state: Arc<AppState>) {
+    let token = std::env::var("USENETSYNC_DAEMON_TOKEN").unwrap_or_default();
+    let trusted_keys = trusted_identity_keys();
+    let (read_half, mut write_half) = socket.into_split();
+    let mut lines = BufReader::new(read_half).lines();
+    let mut events = crate::unified_backend::subscribe_events();
+
+    // No token configured means the daemon is intentionally left
+    // unauthenticated (matches running the GUI on localhost). Otherwise a
+    // fresh connection starts unauthenticated, and the `if authenticated`
+    // guard below keeps it from receiving a single forwarded event --
+    // which would otherwise start the instant the TCP connection opens --
+    // until it has presented the token (or a trusted identity proof) on a
+    // request.
+    let mut authenticated = token.is_empty();
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let line = match line {
+                    Ok(Some(line)) => line,
+                    _ => break,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let response = handle_request(&line, &token, &trusted_keys, &mut authenticated, &state).await;
+                if write_json_line(&mut write_half, &response).await.is_err() {
+                    break;
+                }
+            }
+            event = events.recv(), if authenticated => {
+                match event {
+                    // Log lines have their own dedicated stream
+                    // (`start_log_stream`); everything else here is
+                    // transfer/segment progress worth forwarding.
+                    Ok(event) if event.event != "log_line" => {
+                        if write_json_line(&mut write_half, &event).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+}
+
+/// Compare two bearer tokens in constant time, so a failed guess can't be
+/// distinguished by how long the comparison took.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn handle_request(
+    line: &str,
+    token: &str,
+    trusted_keys: &[Vec<u8>],
+    authenticated: &mut bool,
+    state: &Arc<AppState>,
+) -> DaemonResponse {
+    let request = match serde_json::from_str::<DaemonRequest>(line) {
+        Ok(request) => request,
+        Err(e) => {
+            return DaemonResponse {
+                success: false,
+                data: None,
+                error: Some(format!("invalid request: {}", e)),
+            }
+        }
+    };
+
+    if !token.is_empty() {
+        let token_ok = constant_time_eq(&request.token, token);
+        let identity_ok = request
+            .identity_proof
+            .as_ref()
+            .zip(request.identity_public_key.as_deref())
+            .and_then(|(proof, key)| base64::decode(key).ok().map(|key| (proof, key)))
+            .is_some_and(|(proof, key)| {
+                trusted_keys.iter().any(|trusted| trusted == &key)
+                    && state
+                        .identity_manager
+                        .lock()
+                        .unwrap()
+                        .verify_identity_proof(proof, &key, crate::identity::DEFAULT_PROOF_VALIDITY_WINDOW_SECS)
+                        .unwrap_or(false)
+            });
+
+        if !token_ok && !identity_ok {
+            return DaemonResponse {
+                success: false,
+                data: None,
+                error: Some("invalid token".to_string()),
+            };
+        }
+        *authenticated = true;
+    }
+
+    match execute_unified_command_licensed(&request.command, request.args, state, None).await {
+        Ok(result) => DaemonResponse {
+            success: result.success,
+            data: result.data,
+            error: result.error,
+        },
+        Err(e) => DaemonResponse { success: false, data: None, error: Some(e) },
+    }
+}
+
+async fn write_json_line<T: Serialize>(
+    write_half: &mut tokio::net::tcp::OwnedWriteHalf,
+    value: &T,
+) -> std::io::Result<()> {
+    let mut payload = serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string());
+    payload.push('\n');
+    write_half.write_all(payload.as_bytes()).await
+}
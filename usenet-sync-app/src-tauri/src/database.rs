@@ -0,0 +1,105 @@
+// Pluggable database backend selection: SQLite by default, PostgreSQL as an
+// opt-in feature, following vaultwarden's multi-backend feature-flag
+// design.
+//
+// The actual schema, migrations, and connection pooling all live in the
+// opaque backend process behind `execute_unified_command` -- there's no
+// Rust-side sqlx/rusqlite/tokio-postgres client in this tree. What this
+// module owns is which backend the app is configured to use, and routing
+// `check_database_status`/`setup_database`/`migrate_database` to it with
+// that choice attached: the same "Rust holds the decision, the backend
+// does the work" split as `backend_pool.rs`.
+
+#[cfg(not(any(feature = "sqlite", feature = "postgresql")))]
+compile_error!("at least one of the `sqlite` or `postgresql` features must be enabled");
+
+use serde::{Deserialize, Serialize};
+
+use crate::unified_backend::execute_unified_command;
+use crate::AppState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseBackend {
+    Sqlite,
+    Postgresql,
+}
+
+impl DatabaseBackend {
+    fn as_str(self) -> &'static str {
+        match self {
+            DatabaseBackend::Sqlite => "sqlite",
+            DatabaseBackend::Postgresql => "postgresql",
+        }
+    }
+}
+
+/// The backend this build is configured to use: SQLite unless
+/// `USENETSYNC_DB_BACKEND=postgresql` is set on a build compiled with the
+/// `postgresql` feature enabled; a build compiled without the `sqlite`
+/// feature always uses PostgreSQL. Defaults to the embedded backend so a
+/// casual install needs no external service, mirroring vaultwarden's
+/// `DB_BACKEND` resolution.
+pub fn active() -> DatabaseBackend {
+    #[cfg(feature = "postgresql")]
+    {
+        if std::env::var("USENETSYNC_DB_BACKEND").as_deref() == Ok("postgresql") || !cfg!(feature = "sqlite") {
+            return DatabaseBackend::Postgresql;
+        }
+    }
+    DatabaseBackend::Sqlite
+}
+
+/// Check the active backend's connection/schema status.
+pub async fn check_status(app_state: &AppState) -> Result<serde_json::Value, String> {
+    let args = serde_json::json!({ "backend": active().as_str() });
+
+    let result = app_state
+        .backend_pool
+        .call_cached("check_database_status", args)
+        .await
+        .map_err(|e| format!("Failed to check database status: {}", e))?;
+
+    if result.success {
+        Ok(result.data.unwrap_or(serde_json::json!({})))
+    } else {
+        Err(result.error.unwrap_or_else(|| "Command failed".to_string()))
+    }
+}
+
+/// Provision `backend` -- creating an embedded SQLite file or
+/// connecting/initializing schema on a configured Postgres server.
+pub async fn setup(backend: DatabaseBackend, config: Option<serde_json::Value>) -> Result<serde_json::Value, String> {
+    let command = match backend {
+        DatabaseBackend::Sqlite => "setup_sqlite",
+        DatabaseBackend::Postgresql => "setup_postgresql",
+    };
+    let args = serde_json::json!({ "config": config });
+
+    let result = execute_unified_command(command, args)
+        .await
+        .map_err(|e| format!("Failed to set up database: {}", e))?;
+
+    if result.success {
+        Ok(result.data.unwrap_or(serde_json::json!({})))
+    } else {
+        Err(result.error.unwrap_or_else(|| "Command failed".to_string()))
+    }
+}
+
+/// Move folder/share metadata from `from` to `to` without losing it, e.g.
+/// when an existing Postgres user wants to switch to the simpler embedded
+/// SQLite backend or vice versa.
+pub async fn migrate(from: DatabaseBackend, to: DatabaseBackend) -> Result<serde_json::Value, String> {
+    let args = serde_json::json!({ "from": from.as_str(), "to": to.as_str() });
+
+    let result = execute_unified_command("migrate_database", args)
+        .await
+        .map_err(|e| format!("Failed to migrate database: {}", e))?;
+
+    if result.success {
+        Ok(result.data.unwrap_or(serde_json::json!({})))
+    } else {
+        Err(result.error.unwrap_or_else(|| "Command failed".to_string()))
+    }
+}
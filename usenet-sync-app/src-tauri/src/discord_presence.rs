@@ -0,0 +1,97 @@
+// Optional Discord Rich Presence integration, showing what the app is
+// currently transferring in the user's Discord profile.
+//
+// `discord_rich_presence`'s IPC client talks to the local Discord process
+// over a blocking Unix socket/named pipe, as the openmultiplayer launcher's
+// presence integration does, so this runs its own `std::thread::spawn`
+// loop rather than a tokio task -- there's nothing to gain from wrapping a
+// blocking handshake in `spawn_blocking` just for a background presence
+// updater. Spawned once at startup; a no-op, and fully silent if no
+// Discord client is installed, whenever `AppState::discord_presence_enabled`
+// is false.
+
+use std::time::Duration;
+
+use discord_rich_presence::activity::{Activity, Timestamps};
+use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+use tauri::Manager;
+
+use crate::AppState;
+
+/// Discord rate-limits presence updates; this comfortably avoids tripping
+/// that limit while still feeling live.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(15);
+
+const DISCORD_CLIENT_ID: &str = "usenet-sync";
+
+/// Connect/update/disconnect from Discord on [`UPDATE_INTERVAL`] forever,
+/// reading `AppState::discord_presence_enabled` each tick and the latest
+/// [`crate::stats_sampler`] snapshot for what to show.
+pub fn watch(app_handle: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut client: Option<DiscordIpcClient> = None;
+        let started_at = unix_now();
+
+        loop {
+            std::thread::sleep(UPDATE_INTERVAL);
+
+            let enabled = *app_handle.state::<AppState>().discord_presence_enabled.lock().unwrap();
+
+            if !enabled {
+                if let Some(mut c) = client.take() {
+                    let _ = c.close();
+                }
+                continue;
+            }
+
+            if client.is_none() {
+                client = connect();
+                if client.is_none() {
+                    continue;
+                }
+            }
+
+            let stats = crate::stats_sampler::latest();
+            let state = presence_state(&stats);
+
+            if let Some(c) = client.as_mut() {
+                let activity = Activity::new()
+                    .state(&state)
+                    .details("usenet-sync")
+                    .timestamps(Timestamps::new().start(started_at));
+
+                if c.set_activity(activity).is_err() {
+                    // Discord likely closed; drop and try reconnecting next tick.
+                    client = None;
+                }
+            }
+        }
+    });
+}
+
+fn connect() -> Option<DiscordIpcClient> {
+    let mut client = DiscordIpcClient::new(DISCORD_CLIENT_ID).ok()?;
+    client.connect().ok()?;
+    Some(client)
+}
+
+fn presence_state(stats: &crate::SystemStats) -> String {
+    if stats.active_transfers == 0 {
+        return "Idle".to_string();
+    }
+
+    let mbps = (stats.network_speed.upload + stats.network_speed.download) / 1_000_000.0;
+    format!(
+        "{} active transfer{} · {:.1} MB/s",
+        stats.active_transfers,
+        if stats.active_transfers == 1 { "" } else { "s" },
+        mbps
+    )
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,212 @@
+// RSS/Atom feed watcher for automatic indexing and download.
+//
+// Polls configured feeds on an interval, parses entries with `feed-rs`,
+// dedupes against already-seen entry GUIDs, and for each new entry
+// matching the feed's filter rules hands the entry's share reference to
+// the existing `download_share` pipeline -- Sonarr/SABnzbd-style
+// automation on top of plumbing the app already has.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::Emitter;
+
+use crate::unified_backend::execute_unified_command;
+
+/// How often each configured feed is re-fetched.
+const POLL_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Where auto-downloaded shares land when a feed match triggers a
+/// download, mirroring the manual `download_share` default of "wherever
+/// the user points it" with a sensible unattended fallback.
+fn default_destination() -> PathBuf {
+    dirs::download_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("usenet-sync")
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FeedFilter {
+    #[serde(rename = "titleRegex")]
+    pub title_regex: Option<String>,
+    #[serde(rename = "minSize")]
+    pub min_size: Option<u64>,
+    #[serde(rename = "maxSize")]
+    pub max_size: Option<u64>,
+}
+
+impl FeedFilter {
+    fn matches(&self, title: &str, size: Option<u64>) -> bool {
+        if let Some(pattern) = &self.title_regex {
+            match Regex::new(pattern) {
+                Ok(re) if re.is_match(title) => {}
+                _ => return false,
+            }
+        }
+        if let Some(min_size) = self.min_size {
+            if size.unwrap_or(0) < min_size {
+                return false;
+            }
+        }
+        if let Some(max_size) = self.max_size {
+            if size.unwrap_or(u64::MAX) > max_size {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Feed {
+    pub id: String,
+    pub url: String,
+    pub filter: FeedFilter,
+    #[serde(rename = "seenGuids", default)]
+    seen_guids: HashSet<String>,
+}
+
+/// A new feed entry that matched its feed's filter and was enqueued for
+/// download, emitted to the UI as a `"feed-activity"` event.
+#[derive(Debug, Serialize, Clone)]
+pub struct FeedActivity {
+    #[serde(rename = "feedId")]
+    pub feed_id: String,
+    pub title: String,
+    #[serde(rename = "shareId")]
+    pub share_id: String,
+}
+
+struct FeedStore {
+    path: PathBuf,
+    feeds: Mutex<Vec<Feed>>,
+}
+
+static STORE: Lazy<FeedStore> = Lazy::new(FeedStore::load);
+
+fn store_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("usenet-sync")
+        .join("feeds.json")
+}
+
+impl FeedStore {
+    fn load() -> Self {
+        let path = store_path();
+        let feeds = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path, feeds: Mutex::new(feeds) }
+    }
+
+    fn save(&self) {
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&*self.feeds.lock().unwrap()) {
+            let _ = fs::write(&self.path, json);
+        }
+    }
+}
+
+pub fn add_feed(url: String, filter: FeedFilter) -> Feed {
+    let feed = Feed { id: uuid::Uuid::new_v4().to_string(), url, filter, seen_guids: HashSet::new() };
+    STORE.feeds.lock().unwrap().push(feed.clone());
+    STORE.save();
+    feed
+}
+
+pub fn list_feeds() -> Vec<Feed> {
+    STORE.feeds.lock().unwrap().clone()
+}
+
+pub fn remove_feed(feed_id: &str) {
+    STORE.feeds.lock().unwrap().retain(|f| f.id != feed_id);
+    STORE.save();
+}
+
+/// Poll every configured feed on [`POLL_INTERVAL`] forever, downloading
+/// new matches and emitting a `"feed-activity"` event for each. Intended
+/// to be spawned once at startup from `tauri::Builder::setup`, where an
+/// `AppHandle` is available to emit on.
+pub async fn watch(app_handle: tauri::AppHandle) {
+    let mut ticker = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        ticker.tick().await;
+        for feed in list_feeds() {
+            if let Err(e) = poll_feed(&feed, &app_handle).await {
+                eprintln!("feeds: failed to poll {}: {}", feed.url, e);
+            }
+        }
+    }
+}
+
+async fn poll_feed(feed: &Feed, app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let body = reqwest::get(&feed.url)
+        .await
+        .map_err(|e| e.to_string())?
+        .bytes()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let parsed = feed_rs::parser::parse(&body[..]).map_err(|e| e.to_string())?;
+
+    let mut newly_seen = Vec::new();
+    for entry in parsed.entries {
+        if feed.seen_guids.contains(&entry.id) {
+            continue;
+        }
+
+        let title = entry.title.map(|t| t.content).unwrap_or_default();
+        let size = entry.media.iter().flat_map(|m| &m.content).find_map(|c| c.size);
+
+        if !feed.filter.matches(&title, size) {
+            // Doesn't match this feed's filter and never will (the entry
+            // itself doesn't change) -- safe to mark seen so it isn't
+            // re-evaluated every poll.
+            newly_seen.push(entry.id.clone());
+            continue;
+        }
+
+        let share_id = entry.id.clone();
+        let args = serde_json::json!({
+            "share_id": share_id,
+            "destination": default_destination().to_string_lossy(),
+            "selected_files": serde_json::Value::Null,
+        });
+
+        match execute_unified_command("download_share", args).await {
+            Ok(result) if result.success => {
+                // Only mark seen once the download actually succeeded --
+                // a transient backend failure should leave the entry
+                // eligible for retry on the next poll instead of silently
+                // dropping it forever.
+                newly_seen.push(entry.id.clone());
+                let _ = app_handle.emit(
+                    "feed-activity",
+                    &FeedActivity { feed_id: feed.id.clone(), title, share_id },
+                );
+            }
+            _ => continue,
+        }
+    }
+
+    if !newly_seen.is_empty() {
+        let mut feeds = STORE.feeds.lock().unwrap();
+        if let Some(stored) = feeds.iter_mut().find(|f| f.id == feed.id) {
+            stored.seen_guids.extend(newly_seen);
+        }
+        drop(feeds);
+        STORE.save();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,260 @@
+// Fuzzy hardware fingerprint
+//
+// A single hash over CPU brand + first MAC + total memory invalidates a
+// license binding on any small change (a new virtual NIC, a RAM upgrade).
+// This collects several independent hardware signals, hashes each one
+// separately, and accepts a machine whose current fingerprint shares at
+// least a configurable threshold of components with the reference
+// fingerprint recorded at activation time — tolerating bounded drift while
+// still rejecting a genuinely different machine.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sysinfo::{Disks, Networks, System};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ComponentHash {
+    pub name: String,
+    pub hash: String,
+}
+
+/// The set of independently-hashed hardware signals for a machine, taken
+/// at a point in time. Components are named so two fingerprints can be
+/// compared signal-by-signal instead of all-or-nothing.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HardwareFingerprint {
+    pub components: Vec<ComponentHash>,
+}
+
+/// How many reference components a candidate fingerprint must still match
+/// to be accepted as "the same machine".
+#[derive(Debug, Clone, Copy)]
+pub struct FingerprintConfig {
+    pub match_threshold: usize,
+}
+
+impl Default for FingerprintConfig {
+    fn default() -> Self {
+        // A machine with 5-6 recorded components (CPU, a couple of NICs,
+        // memory tier, one or two disks) tolerates two or three of them
+        // drifting before re-activation is forced.
+        Self { match_threshold: 3 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FingerprintMatch {
+    pub matched: usize,
+    pub reference_total: usize,
+    pub accepted: bool,
+}
+
+fn hash_component(value: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(value.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Bucket total memory into coarse tiers so a RAM upgrade within the same
+/// tier doesn't register as a changed component.
+fn memory_tier(total_memory_kb: u64) -> &'static str {
+    let gb = total_memory_kb / (1024 * 1024);
+    match gb {
+        0..=4 => "<=4GB",
+        5..=8 => "8GB",
+        9..=16 => "16GB",
+        17..=32 => "32GB",
+        33..=64 => "64GB",
+        _ => ">64GB",
+    }
+}
+
+/// Collect the current machine's fingerprint components. Any signal that
+/// isn't available on this platform (no disks enumerated, no NICs, etc.)
+/// is simply omitted rather than counted as a mismatch later.
+pub fn current_fingerprint() -> HardwareFingerprint {
+    let mut sys = System::new_all();
+    sys.refresh_all();
+
+    let mut components = Vec::new();
+
+    if let Some(cpu) = sys.cpus().first() {
+        components.push(ComponentHash {
+            name: "cpu_brand".to_string(),
+            hash: hash_component(cpu.brand()),
+        });
+    }
+
+    let networks = Networks::new_with_refreshed_list();
+    for (name, data) in networks.iter() {
+        let mac = data.mac_address().to_string();
+        if mac == "00:00:00:00:00:00" {
+            continue;
+        }
+        components.push(ComponentHash {
+            name: format!("nic:{}", name),
+            hash: hash_component(&mac),
+        });
+    }
+
+    components.push(ComponentHash {
+        name: "memory_tier".to_string(),
+        hash: hash_component(memory_tier(sys.total_memory() / 1024)),
+    });
+
+    let disks = Disks::new_with_refreshed_list();
+    for disk in disks.iter() {
+        let id = disk.name().to_string_lossy().to_string();
+        components.push(ComponentHash {
+            name: format!("disk:{}", id),
+            hash: hash_component(&id),
+        });
+    }
+
+    if let Some(host_name) = System::host_name() {
+        components.push(ComponentHash {
+            name: "machine_id".to_string(),
+            hash: hash_component(&host_name),
+        });
+    }
+
+    HardwareFingerprint { components }
+}
+
+/// Count how many of `reference`'s components `candidate` still matches
+/// by name and hash.
+pub fn matched_components(reference: &HardwareFingerprint, candidate: &HardwareFingerprint) -> usize {
+    reference
+        .components
+        .iter()
+        .filter(|r| {
+            candidate
+                .components
+                .iter()
+                .any(|c| c.name == r.name && c.hash == r.hash)
+        })
+        .count()
+}
+
+/// Compare `candidate` against `reference`, accepting it when at least
+/// `config.match_threshold` components still agree. The threshold is
+/// capped at `reference`'s own component count: a machine that only ever
+/// yields 1-2 signals (no disks enumerated, no NIC with a real MAC --
+/// common on minimal VMs/containers) would otherwise never be accepted,
+/// even against its own identical fingerprint, since `matched` can never
+/// exceed `reference_total`.
+pub fn verify_fingerprint(
+    reference: &HardwareFingerprint,
+    candidate: &HardwareFingerprint,
+    config: &FingerprintConfig,
+) -> FingerprintMatch {
+    let matched = matched_components(reference, candidate);
+    let reference_total = reference.components.len();
+    let threshold = config.match_threshold.min(reference_total);
+    FingerprintMatch {
+        matched,
+        reference_total,
+        accepted: matched >= threshold,
+    }
+}
+
+/// Collapse a fingerprint into a single stable string, for callers (like
+/// `LicenseStatus`) that still want one opaque hardware id to display.
+/// Components are sorted by name first so enumeration order (which varies
+/// run to run for networks/disks) doesn't change the result.
+pub fn summary_hash(fingerprint: &HardwareFingerprint) -> String {
+    let mut sorted = fingerprint.components.clone();
+    sorted.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut hasher = Sha256::new();
+    for component in &sorted {
+        hasher.update(component.name.as_bytes());
+        hasher.update(b":");
+        hasher.update(component.hash.as_bytes());
+        hasher.update(b";");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(name: &str, value: &str) -> ComponentHash {
+        ComponentHash {
+            name: name.to_string(),
+            hash: hash_component(value),
+        }
+    }
+
+    fn reference_fingerprint() -> HardwareFingerprint {
+        HardwareFingerprint {
+            components: vec![
+                component("cpu_brand", "Generic CPU"),
+                component("nic:eth0", "AA:BB:CC:DD:EE:FF"),
+                component("nic:eth1", "11:22:33:44:55:66"),
+                component("memory_tier", "16GB"),
+                component("disk:sda", "sda"),
+            ],
+        }
+    }
+
+    #[test]
+    fn identical_fingerprint_matches_every_component() {
+        let reference = reference_fingerprint();
+        let candidate = reference.clone();
+
+        let result = verify_fingerprint(&reference, &candidate, &FingerprintConfig::default());
+        assert_eq!(result.matched, 5);
+        assert!(result.accepted);
+    }
+
+    #[test]
+    fn tolerates_drift_within_threshold() {
+        let reference = reference_fingerprint();
+        // Simulate a new virtual NIC and a RAM upgrade crossing a tier:
+        // only 3 of 5 reference components still match.
+        let mut candidate = reference.clone();
+        candidate.components[1] = component("nic:eth1", "99:99:99:99:99:99");
+        candidate.components[3] = component("memory_tier", "32GB");
+
+        let config = FingerprintConfig { match_threshold: 3 };
+        let result = verify_fingerprint(&reference, &candidate, &config);
+        assert_eq!(result.matched, 3);
+        assert!(result.accepted);
+    }
+
+    #[test]
+    fn rejects_a_wholly_different_machine() {
+        let reference = reference_fingerprint();
+        let candidate = HardwareFingerprint {
+            components: vec![
+                component("cpu_brand", "Different CPU"),
+                component("nic:eth0", "00:11:22:33:44:55"),
+                component("memory_tier", "8GB"),
+            ],
+        };
+
+        let result = verify_fingerprint(&reference, &candidate, &FingerprintConfig::default());
+        assert_eq!(result.matched, 0);
+        assert!(!result.accepted);
+    }
+
+    #[test]
+    fn summary_hash_is_stable_regardless_of_component_order() {
+        let reference = reference_fingerprint();
+        let mut shuffled = reference.clone();
+        shuffled.components.reverse();
+
+        assert_eq!(summary_hash(&reference), summary_hash(&shuffled));
+    }
+
+    #[test]
+    fn summary_hash_changes_when_a_component_changes() {
+        let reference = reference_fingerprint();
+        let mut changed = reference.clone();
+        changed.components[0] = component("cpu_brand", "Something Else");
+
+        assert_ne!(summary_hash(&reference), summary_hash(&changed));
+    }
+}
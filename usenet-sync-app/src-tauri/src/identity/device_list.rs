@@ -0,0 +1,357 @@
+// Signed, multi-device identity list, modeled on Comm's identity service.
+//
+// `IdentityManager` hard-binds one `device_fingerprint` per user, by
+// design: there is no recovery or backup path, so a lost machine is a
+// lost account. This module adds real multi-device support without
+// handing trust to a server: a user's original keypair is the *primary*
+// device, and it signs a list of every device's Ed25519 public key.
+// Adding a device appends to the list and re-signs it under a bumped
+// `timestamp`; handing off the primary role to a new device carries a
+// `prev_primary_signature` from the old primary so a verifier can follow
+// the chain of custody instead of just trusting whoever shows up with a
+// newer list. Verification enforces that `timestamp` only moves forward
+// and that the list isn't older than a caller-supplied freshness window.
+
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use serde::{Deserialize, Serialize};
+
+use crate::identity::{IdentityManager, ImmutableIdentity};
+
+/// One device's Ed25519 public key, as carried inside a device list.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DevicePublicKey {
+    pub device_id: String,
+    pub public_key: Vec<u8>,
+    pub added_at: i64,
+}
+
+/// The payload a `SignedDeviceList` signature covers, serialized into
+/// `SignedDeviceList::raw_device_list` so the exact signed bytes are
+/// preserved verbatim between issuance and verification.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeviceListPayload {
+    pub devices: Vec<DevicePublicKey>,
+    pub timestamp: i64,
+}
+
+/// A device list signed by its current primary device, with an optional
+/// trail back to the previous primary for hand-offs.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SignedDeviceList {
+    /// JSON-stringified `DeviceListPayload`. Kept as the literal signed
+    /// string (not re-derived from `DeviceListPayload` at verify time) so
+    /// signature verification is over exactly the bytes that were signed.
+    pub raw_device_list: String,
+    /// Signature over `raw_device_list` by the current primary device.
+    pub primary_signature: Vec<u8>,
+    /// Present only when the primary changed: the outgoing primary's
+    /// signature over the same `raw_device_list`, proving it approved the
+    /// hand-off.
+    pub prev_primary_signature: Option<Vec<u8>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeviceListError {
+    Malformed(String),
+    BadSignature,
+    /// No device's public key in the list verifies `primary_signature`.
+    UnknownPrimary,
+    /// `timestamp` did not move forward relative to the last seen list.
+    NotMonotonic,
+    /// `timestamp` is outside the caller's freshness window.
+    Stale,
+}
+
+impl std::fmt::Display for DeviceListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceListError::Malformed(msg) => write!(f, "malformed device list: {}", msg),
+            DeviceListError::BadSignature => write!(f, "device list signature does not verify"),
+            DeviceListError::UnknownPrimary => {
+                write!(f, "no device in the list signed as primary")
+            }
+            DeviceListError::NotMonotonic => {
+                write!(f, "device list timestamp did not advance")
+            }
+            DeviceListError::Stale => write!(f, "device list is outside the freshness window"),
+        }
+    }
+}
+
+impl std::error::Error for DeviceListError {}
+
+fn verify_raw(raw_device_list: &str, public_key_bytes: &[u8], signature_bytes: &[u8]) -> bool {
+    let public_key = match PublicKey::from_bytes(public_key_bytes) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    let signature = match Signature::from_bytes(signature_bytes) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+    public_key.verify(raw_device_list.as_bytes(), &signature).is_ok()
+}
+
+impl SignedDeviceList {
+    /// Start a brand-new device list containing only the primary itself.
+    pub fn new(
+        identity_manager: &IdentityManager,
+        primary: &ImmutableIdentity,
+        timestamp: i64,
+    ) -> anyhow::Result<Self> {
+        let payload = DeviceListPayload {
+            devices: vec![DevicePublicKey {
+                device_id: primary.user_id.clone(),
+                public_key: primary.public_key.clone(),
+                added_at: timestamp,
+            }],
+            timestamp,
+        };
+        let raw_device_list = serde_json::to_string(&payload)?;
+        let primary_signature = identity_manager.sign_data(primary, raw_device_list.as_bytes())?;
+
+        Ok(Self {
+            raw_device_list,
+            primary_signature,
+            prev_primary_signature: None,
+        })
+    }
+
+    /// Parse `raw_device_list` back into its structured form.
+    pub fn payload(&self) -> Result<DeviceListPayload, DeviceListError> {
+        serde_json::from_str(&self.raw_device_list)
+            .map_err(|e| DeviceListError::Malformed(e.to_string()))
+    }
+
+    /// Append `device` to the list and re-sign it as the current primary,
+    /// bumping `timestamp`. The primary does not change.
+    pub fn add_device(
+        &self,
+        identity_manager: &IdentityManager,
+        primary: &ImmutableIdentity,
+        device: DevicePublicKey,
+        timestamp: i64,
+    ) -> anyhow::Result<Self> {
+        let mut payload = self.payload().map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        payload.devices.push(device);
+        payload.timestamp = timestamp;
+
+        let raw_device_list = serde_json::to_string(&payload)?;
+        let primary_signature = identity_manager.sign_data(primary, raw_device_list.as_bytes())?;
+
+        Ok(Self {
+            raw_device_list,
+            primary_signature,
+            prev_primary_signature: None,
+        })
+    }
+
+    /// Hand the primary role to `new_primary`, which must already be
+    /// present in the device list. `old_primary` co-signs the same bytes
+    /// so verifiers can follow the hand-off back to a trusted root.
+    pub fn rotate_primary(
+        &self,
+        identity_manager: &IdentityManager,
+        old_primary: &ImmutableIdentity,
+        new_primary: &ImmutableIdentity,
+        timestamp: i64,
+    ) -> anyhow::Result<Self> {
+        let mut payload = self.payload().map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        payload.timestamp = timestamp;
+        let raw_device_list = serde_json::to_string(&payload)?;
+
+        let prev_primary_signature =
+            identity_manager.sign_data(old_primary, raw_device_list.as_bytes())?;
+        let primary_signature =
+            identity_manager.sign_data(new_primary, raw_device_list.as_bytes())?;
+
+        Ok(Self {
+            raw_device_list,
+            primary_signature,
+            prev_primary_signature: Some(prev_primary_signature),
+        })
+    }
+}
+
+/// Verify a `SignedDeviceList` against the last primary key a verifier
+/// trusted, enforcing timestamp monotonicity and freshness.
+///
+/// Returns the parsed payload and the public key that should now be
+/// trusted as primary going forward (unchanged unless a verified
+/// hand-off occurred), so callers can pin it for the next verification.
+pub fn verify_device_list(
+    list: &SignedDeviceList,
+    trusted_primary_public_key: &[u8],
+    last_seen_timestamp: Option<i64>,
+    freshness_window_secs: i64,
+    now: i64,
+) -> Result<(DeviceListPayload, Vec<u8>), DeviceListError> {
+    let payload = list.payload()?;
+
+    if let Some(last_seen) = last_seen_timestamp {
+        if payload.timestamp <= last_seen {
+            return Err(DeviceListError::NotMonotonic);
+        }
+    }
+
+    if (now - payload.timestamp).abs() > freshness_window_secs {
+        return Err(DeviceListError::Stale);
+    }
+
+    if let Some(prev_primary_signature) = &list.prev_primary_signature {
+        // Primary hand-off: the outgoing primary must have co-signed the
+        // same bytes, and the new primary's key must be one of the
+        // devices on the list.
+        if !verify_raw(&list.raw_device_list, trusted_primary_public_key, prev_primary_signature) {
+            return Err(DeviceListError::BadSignature);
+        }
+
+        for device in &payload.devices {
+            if verify_raw(&list.raw_device_list, &device.public_key, &list.primary_signature) {
+                return Ok((payload, device.public_key.clone()));
+            }
+        }
+        Err(DeviceListError::UnknownPrimary)
+    } else {
+        // Same primary as before: its signature alone must verify.
+        if verify_raw(&list.raw_device_list, trusted_primary_public_key, &list.primary_signature) {
+            Ok((payload, trusted_primary_public_key.to_vec()))
+        } else {
+            Err(DeviceListError::BadSignature)
+        }
+    }
+}
+
+/// Whether `device_id` appears anywhere in a verified device list.
+pub fn device_list_contains(payload: &DeviceListPayload, device_id: &str) -> bool {
+    payload.devices.iter().any(|device| device.device_id == device_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_manager_and_identity() -> (IdentityManager, ImmutableIdentity) {
+        let mut manager = IdentityManager::new();
+        let (identity, _is_new) = manager.initialize_identity().unwrap();
+        (manager, identity)
+    }
+
+    #[test]
+    fn a_fresh_list_contains_only_the_primary_and_verifies() {
+        let (manager, primary) = new_manager_and_identity();
+        let list = SignedDeviceList::new(&manager, &primary, 1_000).unwrap();
+
+        let (payload, trusted_key) =
+            verify_device_list(&list, &primary.public_key, None, 3_600, 1_000).unwrap();
+
+        assert_eq!(payload.devices.len(), 1);
+        assert_eq!(trusted_key, primary.public_key);
+        assert!(device_list_contains(&payload, &primary.user_id));
+    }
+
+    #[test]
+    fn adding_a_device_keeps_the_same_primary() {
+        let (manager, primary) = new_manager_and_identity();
+        let list = SignedDeviceList::new(&manager, &primary, 1_000).unwrap();
+
+        let new_device = DevicePublicKey {
+            device_id: "laptop-2".to_string(),
+            public_key: vec![7u8; 32],
+            added_at: 2_000,
+        };
+        let updated = list.add_device(&manager, &primary, new_device, 2_000).unwrap();
+
+        let (payload, _trusted_key) =
+            verify_device_list(&updated, &primary.public_key, Some(1_000), 3_600, 2_000).unwrap();
+        assert_eq!(payload.devices.len(), 2);
+        assert!(device_list_contains(&payload, "laptop-2"));
+    }
+
+    #[test]
+    fn rejects_a_timestamp_that_did_not_advance() {
+        let (manager, primary) = new_manager_and_identity();
+        let list = SignedDeviceList::new(&manager, &primary, 1_000).unwrap();
+
+        assert_eq!(
+            verify_device_list(&list, &primary.public_key, Some(1_000), 3_600, 1_000),
+            Err(DeviceListError::NotMonotonic)
+        );
+    }
+
+    #[test]
+    fn rejects_a_list_outside_the_freshness_window() {
+        let (manager, primary) = new_manager_and_identity();
+        let list = SignedDeviceList::new(&manager, &primary, 1_000).unwrap();
+
+        assert_eq!(
+            verify_device_list(&list, &primary.public_key, None, 60, 10_000),
+            Err(DeviceListError::Stale)
+        );
+    }
+
+    #[test]
+    fn rejects_a_list_signed_by_an_untrusted_key() {
+        let (manager, primary) = new_manager_and_identity();
+        let list = SignedDeviceList::new(&manager, &primary, 1_000).unwrap();
+
+        let other_keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+
+        assert_eq!(
+            verify_device_list(&list, &other_keypair.public.to_bytes(), None, 3_600, 1_000),
+            Err(DeviceListError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn a_primary_hand_off_is_verified_against_the_old_primary() {
+        let (manager, old_primary) = new_manager_and_identity();
+        let list = SignedDeviceList::new(&manager, &old_primary, 1_000).unwrap();
+
+        let (_new_manager, new_primary_identity) = new_manager_and_identity();
+        let new_device = DevicePublicKey {
+            device_id: new_primary_identity.user_id.clone(),
+            public_key: new_primary_identity.public_key.clone(),
+            added_at: 1_500,
+        };
+        let with_new_device = list
+            .add_device(&manager, &old_primary, new_device, 1_500)
+            .unwrap();
+
+        let handed_off = with_new_device
+            .rotate_primary(&manager, &old_primary, &new_primary_identity, 2_000)
+            .unwrap();
+
+        let (payload, trusted_key) =
+            verify_device_list(&handed_off, &old_primary.public_key, Some(1_500), 3_600, 2_000)
+                .unwrap();
+        assert_eq!(trusted_key, new_primary_identity.public_key);
+        assert_eq!(payload.devices.len(), 2);
+    }
+
+    #[test]
+    fn rejects_a_hand_off_not_co_signed_by_the_old_primary() {
+        let (manager, old_primary) = new_manager_and_identity();
+        let list = SignedDeviceList::new(&manager, &old_primary, 1_000).unwrap();
+
+        use ed25519_dalek::Signer;
+        let new_primary_keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        // Forge a hand-off where `prev_primary_signature` is actually just
+        // the new primary signing again, not the old primary approving.
+        let raw_device_list = list.raw_device_list.clone();
+        let forged_prev_signature = new_primary_keypair
+            .sign(raw_device_list.as_bytes())
+            .to_bytes()
+            .to_vec();
+        let forged = SignedDeviceList {
+            raw_device_list,
+            primary_signature: forged_prev_signature.clone(),
+            prev_primary_signature: Some(forged_prev_signature),
+        };
+
+        assert_eq!(
+            verify_device_list(&forged, &old_primary.public_key, None, 3_600, 1_000),
+            Err(DeviceListError::BadSignature)
+        );
+    }
+}
@@ -4,10 +4,72 @@ use serde::{Deserialize, Serialize};
 use sha3::{Sha3_256, Digest};
 use rand::rngs::OsRng;
 use zeroize::Zeroize;
+use std::collections::{HashSet, VecDeque};
 use std::time::{SystemTime, UNIX_EPOCH};
 use anyhow::{Result, anyhow};
 use sysinfo::{System, SystemExt, NetworkExt};
 
+/// Default [`IdentityManager::verify_identity_proof`] validity window, in
+/// the spirit of Comm's device-list-timestamp-valid-for pattern.
+pub const DEFAULT_PROOF_VALIDITY_WINDOW_SECS: i64 = 300;
+
+/// How many `(user_id, nonce)` pairs [`IdentityManager::verify_identity_proof`]
+/// remembers before evicting the oldest. Bounded so a long-lived peer
+/// handshake listener can't be grown into an unbounded memory leak just by
+/// sending it proofs.
+const NONCE_CACHE_CAPACITY: usize = 10_000;
+
+/// Least-recently-inserted cache of `(user_id, nonce)` pairs seen within
+/// the validity window — the anti-replay core of `verify_identity_proof`.
+/// A proof replayed with the same nonce is rejected even though its
+/// signature and timestamp are both still valid.
+struct NonceCache {
+    seen: HashSet<(String, Vec<u8>)>,
+    order: VecDeque<(String, Vec<u8>)>,
+    capacity: usize,
+}
+
+impl NonceCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Returns `true` if `(user_id, nonce)` was already present (i.e. this
+    /// proof is a replay); otherwise records it and returns `false`.
+    fn check_and_insert(&mut self, user_id: &str, nonce: &[u8]) -> bool {
+        let key = (user_id.to_string(), nonce.to_vec());
+        if self.seen.contains(&key) {
+            return true;
+        }
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.order.push_back(key.clone());
+        self.seen.insert(key);
+        false
+    }
+}
+
+/// Signed, multi-device identity lists (a user's keypair acting as a
+/// primary device that can authorize additional devices). See
+/// `device_list` for details.
+mod device_list;
+pub use device_list::{
+    device_list_contains, verify_device_list, DeviceListError, DevicePublicKey, DeviceListPayload,
+    SignedDeviceList,
+};
+
+/// Matrix-style SAS (emoji) out-of-band device verification. See `sas`
+/// for details.
+mod sas;
+pub use sas::{verify_attestation, Sas, SasError, VerificationAttestation};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ImmutableIdentity {
     pub user_id: String,
@@ -29,6 +91,7 @@ pub struct IdentityManager {
     keyring_service: String,
     keyring_user: String,
     identity_cache: Option<ImmutableIdentity>,
+    nonce_cache: NonceCache,
 }
 
 impl IdentityManager {
@@ -37,6 +100,7 @@ impl IdentityManager {
             keyring_service: "UsenetSync".to_string(),
             keyring_user: "Identity".to_string(),
             identity_cache: None,
+            nonce_cache: NonceCache::new(NONCE_CACHE_CAPACITY),
         }
     }
     
@@ -134,7 +198,33 @@ impl IdentityManager {
         let current_fingerprint = self.generate_device_fingerprint()?;
         Ok(current_fingerprint == identity.device_fingerprint)
     }
-    
+
+    /// Multi-device-aware alternative to [`Self::verify_device`]: accepts
+    /// any device present in a verified [`SignedDeviceList`] rather than
+    /// only the single hardware fingerprint an identity was first created
+    /// with. `trusted_primary_public_key`/`last_seen_timestamp` should be
+    /// whatever this caller last pinned; on success it gets back the
+    /// public key to pin going forward (unchanged unless the list carried
+    /// a verified primary hand-off).
+    pub fn verify_device_in_list(
+        &self,
+        device_list: &SignedDeviceList,
+        device_id: &str,
+        trusted_primary_public_key: &[u8],
+        last_seen_timestamp: Option<i64>,
+        freshness_window_secs: i64,
+        now: i64,
+    ) -> std::result::Result<(bool, Vec<u8>), DeviceListError> {
+        let (payload, trusted_primary_public_key) = verify_device_list(
+            device_list,
+            trusted_primary_public_key,
+            last_seen_timestamp,
+            freshness_window_secs,
+            now,
+        )?;
+        Ok((device_list_contains(&payload, device_id), trusted_primary_public_key))
+    }
+
     pub fn sign_data(&self, identity: &ImmutableIdentity, data: &[u8]) -> Result<Vec<u8>> {
         // Retrieve private key from keychain
         let private_entry = Entry::new(&self.keyring_service, &format!("{}_private", identity.user_id))?;
@@ -184,7 +274,45 @@ impl IdentityManager {
             signature,
         })
     }
-    
+
+    /// Verify a challenge-response [`IdentityProof`] from a peer: checks
+    /// the Ed25519 signature against `peer_public_key`, rejects a
+    /// `timestamp` more than `validity_window_secs` away from now, and
+    /// rejects a `(user_id, nonce)` pair already seen within that window
+    /// (i.e. a replayed proof). Only a malformed public key or signature
+    /// bubble up as `Err`; every other rejection is `Ok(false)`, since a
+    /// bad proof from a peer is an expected outcome, not a local failure.
+    pub fn verify_identity_proof(
+        &mut self,
+        proof: &IdentityProof,
+        peer_public_key: &[u8],
+        validity_window_secs: i64,
+    ) -> Result<bool> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs() as i64;
+        if (now - proof.timestamp).abs() > validity_window_secs {
+            return Ok(false);
+        }
+
+        let mut proof_data = Vec::new();
+        proof_data.extend_from_slice(proof.user_id.as_bytes());
+        proof_data.extend_from_slice(&proof.timestamp.to_le_bytes());
+        proof_data.extend_from_slice(&proof.nonce);
+
+        let public_key = PublicKey::from_bytes(peer_public_key)?;
+        let signature = Signature::from_bytes(&proof.signature)?;
+        if public_key.verify(&proof_data, &signature).is_err() {
+            return Ok(false);
+        }
+
+        if self.nonce_cache.check_and_insert(&proof.user_id, &proof.nonce) {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
     pub fn export_public_identity(&self, identity: &ImmutableIdentity) -> String {
         // Export only public information (no private keys)
         let public_export = serde_json::json!({
@@ -253,4 +381,60 @@ mod tests {
         let fingerprint2 = manager.generate_device_fingerprint().unwrap();
         assert_eq!(fingerprint1, fingerprint2);
     }
+
+    #[test]
+    fn verifies_a_well_formed_identity_proof() {
+        let mut manager = IdentityManager::new();
+        let (identity, _) = manager.initialize_identity().unwrap();
+        let proof = manager.create_identity_proof(&identity).unwrap();
+
+        let verified = manager
+            .verify_identity_proof(&proof, &identity.public_key, DEFAULT_PROOF_VALIDITY_WINDOW_SECS)
+            .unwrap();
+        assert!(verified);
+    }
+
+    #[test]
+    fn rejects_a_replayed_identity_proof() {
+        let mut manager = IdentityManager::new();
+        let (identity, _) = manager.initialize_identity().unwrap();
+        let proof = manager.create_identity_proof(&identity).unwrap();
+
+        assert!(manager
+            .verify_identity_proof(&proof, &identity.public_key, DEFAULT_PROOF_VALIDITY_WINDOW_SECS)
+            .unwrap());
+        assert!(!manager
+            .verify_identity_proof(&proof, &identity.public_key, DEFAULT_PROOF_VALIDITY_WINDOW_SECS)
+            .unwrap());
+    }
+
+    #[test]
+    fn rejects_a_proof_with_a_timestamp_outside_the_validity_window() {
+        let mut manager = IdentityManager::new();
+        let (identity, _) = manager.initialize_identity().unwrap();
+        let mut proof = manager.create_identity_proof(&identity).unwrap();
+        proof.timestamp -= 3600;
+
+        assert!(!manager
+            .verify_identity_proof(&proof, &identity.public_key, DEFAULT_PROOF_VALIDITY_WINDOW_SECS)
+            .unwrap());
+    }
+
+    #[test]
+    fn rejects_a_proof_verified_against_the_wrong_public_key() {
+        let mut manager = IdentityManager::new();
+        let (identity, _) = manager.initialize_identity().unwrap();
+        let proof = manager.create_identity_proof(&identity).unwrap();
+
+        let mut csprng = OsRng;
+        let other_keypair = Keypair::generate(&mut csprng);
+
+        assert!(!manager
+            .verify_identity_proof(
+                &proof,
+                &other_keypair.public.to_bytes(),
+                DEFAULT_PROOF_VALIDITY_WINDOW_SECS
+            )
+            .unwrap());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,326 @@
+// Matrix-style SAS (Short Authentication String) device verification.
+//
+// Lets two `IdentityManager` instances confirm each other's long-term
+// Ed25519 public key out of band, without any server: each side runs an
+// ephemeral X25519 ECDH, derives a shared secret, and runs HKDF over it
+// plus both user ids and both ephemeral public keys to pick ~64-entry
+// emoji indices that both users read aloud and compare. The commitment
+// step — each side sends a hash of its ephemeral public key before
+// either side reveals the key itself — stops a MITM from picking its own
+// ephemeral key after seeing the peer's, which would otherwise let it
+// steer the shared secret (and so the displayed emoji) toward a chosen
+// value.
+
+use ed25519_dalek::Signature;
+use hkdf::Hkdf;
+use rand::rngs::OsRng;
+use sha2::Sha256;
+use sha3::{Digest, Sha3_256};
+use x25519_dalek::{EphemeralSecret, PublicKey as X25519PublicKey};
+
+use crate::identity::IdentityManager;
+
+/// 64 visually-distinct emoji (2^6, so each maps to exactly 6 bits),
+/// matching the spirit of Matrix's SAS emoji table.
+pub const EMOJI_TABLE: [&str; 64] = [
+    "🐶", "🐱", "🦁", "🐴", "🦄", "🐷", "🐘", "🐰", "🐼", "🐻", "🐸", "🐵", "🐔", "🐧", "🐦", "🐤",
+    "🦆", "🦅", "🦉", "🐺", "🐗", "🦔", "🦋", "🐌", "🐞", "🐜", "🦂", "🐢", "🐍", "🦎", "🐙", "🦑",
+    "🦀", "🐠", "🐟", "🐬", "🐳", "🐋", "🦈", "🐊", "🐅", "🐆", "🦓", "🦍", "🦧", "🦨", "🦛", "🦏",
+    "🐪", "🐫", "🦒", "🦘", "🐃", "🐂", "🐄", "🐎", "🐖", "🐑", "🐐", "🦌", "🐕", "🐩", "🦮", "🐈",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SasError {
+    /// `reveal_peer_ephemeral_key` was called before `receive_peer_commitment`.
+    NoCommitment,
+    /// The revealed ephemeral key doesn't hash to the commitment sent earlier.
+    CommitmentMismatch,
+    /// The local ephemeral secret was already consumed by a prior ECDH.
+    AlreadyRevealed,
+    /// Called before the shared secret/emoji were derived.
+    NotReady,
+    Crypto(String),
+}
+
+impl std::fmt::Display for SasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SasError::NoCommitment => write!(f, "peer commitment not received yet"),
+            SasError::CommitmentMismatch => {
+                write!(f, "peer's revealed ephemeral key does not match its earlier commitment")
+            }
+            SasError::AlreadyRevealed => write!(f, "ephemeral key was already used"),
+            SasError::NotReady => write!(f, "verification has not completed key agreement yet"),
+            SasError::Crypto(msg) => write!(f, "verification crypto error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SasError {}
+
+/// An attestation that `attester_user_id` verified `attested_public_key`
+/// belongs to the peer it ran SAS verification with, signed by the
+/// attester's own identity key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationAttestation {
+    pub attester_user_id: String,
+    pub attested_public_key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+fn commit(ephemeral_public: &X25519PublicKey) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(ephemeral_public.as_bytes());
+    let digest = hasher.finalize();
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&digest);
+    out
+}
+
+/// Split `input` into 6-bit groups (MSB-first) and map each to an emoji.
+fn emoji_from_bits(input: &[u8], count: usize) -> Vec<&'static str> {
+    let mut bits = 0u64;
+    let mut bit_count = 0u32;
+    let mut out = Vec::with_capacity(count);
+
+    for &byte in input {
+        bits = (bits << 8) | byte as u64;
+        bit_count += 8;
+
+        while bit_count >= 6 && out.len() < count {
+            let shift = bit_count - 6;
+            let index = ((bits >> shift) & 0b11_1111) as usize;
+            out.push(EMOJI_TABLE[index]);
+            bit_count -= 6;
+        }
+        if out.len() >= count {
+            break;
+        }
+    }
+    out
+}
+
+/// One side of a single SAS verification session. See the module docs
+/// for the handshake this walks through.
+pub struct Sas {
+    local_user_id: String,
+    peer_user_id: String,
+    ephemeral_secret: Option<EphemeralSecret>,
+    local_ephemeral_public: X25519PublicKey,
+    local_commitment: [u8; 32],
+    peer_commitment: Option<[u8; 32]>,
+    emoji: Option<Vec<&'static str>>,
+}
+
+impl Sas {
+    pub fn new(local_user_id: String, peer_user_id: String) -> Self {
+        let ephemeral_secret = EphemeralSecret::new(OsRng);
+        let local_ephemeral_public = X25519PublicKey::from(&ephemeral_secret);
+        let local_commitment = commit(&local_ephemeral_public);
+
+        Self {
+            local_user_id,
+            peer_user_id,
+            ephemeral_secret: Some(ephemeral_secret),
+            local_ephemeral_public,
+            local_commitment,
+            peer_commitment: None,
+            emoji: None,
+        }
+    }
+
+    /// Send this first, before `local_ephemeral_public`: a commitment to
+    /// our ephemeral key that the peer can check once we reveal it.
+    pub fn local_commitment(&self) -> [u8; 32] {
+        self.local_commitment
+    }
+
+    /// Send this only after both sides have exchanged commitments.
+    pub fn local_ephemeral_public(&self) -> [u8; 32] {
+        self.local_ephemeral_public.to_bytes()
+    }
+
+    /// Step 1 of the handshake: record the peer's commitment. Must be
+    /// called before `reveal_peer_ephemeral_key` so neither side can wait
+    /// to see the other's real ephemeral key before committing to its own.
+    pub fn receive_peer_commitment(&mut self, peer_commitment: [u8; 32]) {
+        self.peer_commitment = Some(peer_commitment);
+    }
+
+    /// Step 2: accept the peer's real ephemeral key, check it against the
+    /// commitment from step 1, run ECDH + HKDF, and derive the emoji.
+    pub fn reveal_peer_ephemeral_key(&mut self, peer_ephemeral_public: [u8; 32]) -> Result<(), SasError> {
+        let expected_commitment = self.peer_commitment.ok_or(SasError::NoCommitment)?;
+        let peer_public = X25519PublicKey::from(peer_ephemeral_public);
+        if commit(&peer_public) != expected_commitment {
+            return Err(SasError::CommitmentMismatch);
+        }
+
+        let ephemeral_secret = self.ephemeral_secret.take().ok_or(SasError::AlreadyRevealed)?;
+        let shared_secret = ephemeral_secret.diffie_hellman(&peer_public);
+
+        // Order both sides by ephemeral public key so each peer feeds HKDF
+        // the same info bytes, whichever side it is.
+        let local_public = self.local_ephemeral_public.to_bytes();
+        let (first_id, second_id, first_public, second_public) = if local_public <= peer_ephemeral_public {
+            (&self.local_user_id, &self.peer_user_id, local_public, peer_ephemeral_public)
+        } else {
+            (&self.peer_user_id, &self.local_user_id, peer_ephemeral_public, local_public)
+        };
+
+        let mut info = Vec::new();
+        info.extend_from_slice(first_id.as_bytes());
+        info.extend_from_slice(second_id.as_bytes());
+        info.extend_from_slice(&first_public);
+        info.extend_from_slice(&second_public);
+
+        let hk = Hkdf::<Sha256>::new(None, shared_secret.as_bytes());
+        let mut okm = [0u8; 8];
+        hk.expand(&info, &mut okm)
+            .map_err(|e| SasError::Crypto(e.to_string()))?;
+
+        // 7 emoji * 6 bits = 42 bits, comfortably inside the 64-bit okm.
+        self.emoji = Some(emoji_from_bits(&okm, 7));
+        Ok(())
+    }
+
+    /// The 7 emoji both sides should read aloud and compare. Only
+    /// available after `reveal_peer_ephemeral_key` succeeds.
+    pub fn emoji(&self) -> Result<&[&'static str], SasError> {
+        self.emoji.as_deref().ok_or(SasError::NotReady)
+    }
+
+    /// Call once the displayed emoji have been confirmed to match out of
+    /// band. Signs the peer's long-term identity public key with this
+    /// device's identity key, producing a portable attestation that this
+    /// user verified it belongs to `peer_user_id`.
+    pub fn confirm(
+        &self,
+        identity_manager: &IdentityManager,
+        local_identity: &crate::identity::ImmutableIdentity,
+        peer_long_term_public_key: &[u8],
+    ) -> Result<VerificationAttestation, SasError> {
+        if self.emoji.is_none() {
+            return Err(SasError::NotReady);
+        }
+
+        let signature = identity_manager
+            .sign_data(local_identity, peer_long_term_public_key)
+            .map_err(|e| SasError::Crypto(e.to_string()))?;
+
+        Ok(VerificationAttestation {
+            attester_user_id: local_identity.user_id.clone(),
+            attested_public_key: peer_long_term_public_key.to_vec(),
+            signature,
+        })
+    }
+}
+
+/// Check a [`VerificationAttestation`] against the attester's own
+/// long-term Ed25519 public key (e.g. one already trusted from a prior
+/// [`super::SignedDeviceList`]).
+pub fn verify_attestation(
+    attestation: &VerificationAttestation,
+    attester_public_key: &[u8],
+) -> Result<bool, SasError> {
+    use ed25519_dalek::{PublicKey, Verifier};
+
+    let public_key =
+        PublicKey::from_bytes(attester_public_key).map_err(|e| SasError::Crypto(e.to_string()))?;
+    let signature = Signature::from_bytes(&attestation.signature)
+        .map_err(|e| SasError::Crypto(e.to_string()))?;
+    Ok(public_key
+        .verify(&attestation.attested_public_key, &signature)
+        .is_ok())
+}
+
+impl IdentityManager {
+    /// Start a new SAS verification session with `peer_user_id`.
+    pub fn start_verification(&self, local_user_id: &str, peer_user_id: &str) -> Sas {
+        Sas::new(local_user_id.to_string(), peer_user_id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::ImmutableIdentity;
+
+    fn manager_and_identity() -> (IdentityManager, ImmutableIdentity) {
+        let mut manager = IdentityManager::new();
+        let (identity, _is_new) = manager.initialize_identity().unwrap();
+        (manager, identity)
+    }
+
+    #[test]
+    fn both_sides_derive_the_same_emoji() {
+        let (manager_a, identity_a) = manager_and_identity();
+        let (manager_b, identity_b) = manager_and_identity();
+
+        let mut sas_a = manager_a.start_verification(&identity_a.user_id, &identity_b.user_id);
+        let mut sas_b = manager_b.start_verification(&identity_b.user_id, &identity_a.user_id);
+
+        // Commitments are exchanged before either ephemeral key is revealed.
+        sas_a.receive_peer_commitment(sas_b.local_commitment());
+        sas_b.receive_peer_commitment(sas_a.local_commitment());
+
+        sas_a.reveal_peer_ephemeral_key(sas_b.local_ephemeral_public()).unwrap();
+        sas_b.reveal_peer_ephemeral_key(sas_a.local_ephemeral_public()).unwrap();
+
+        assert_eq!(sas_a.emoji().unwrap(), sas_b.emoji().unwrap());
+        assert_eq!(sas_a.emoji().unwrap().len(), 7);
+    }
+
+    #[test]
+    fn rejects_an_ephemeral_key_that_does_not_match_its_commitment() {
+        let (manager_a, identity_a) = manager_and_identity();
+        let (_manager_b, identity_b) = manager_and_identity();
+        let (manager_c, identity_c) = manager_and_identity();
+
+        let mut sas_a = manager_a.start_verification(&identity_a.user_id, &identity_b.user_id);
+        let sas_attacker = manager_c.start_verification(&identity_c.user_id, &identity_b.user_id);
+
+        // `sas_attacker`'s commitment is sent, but a different ephemeral
+        // key is revealed afterward (simulating a MITM that tries to swap
+        // in a key it prefers after seeing the real commitment).
+        sas_a.receive_peer_commitment(sas_attacker.local_commitment());
+        let forged_key = Sas::new("forger".to_string(), "victim".to_string()).local_ephemeral_public();
+
+        assert_eq!(
+            sas_a.reveal_peer_ephemeral_key(forged_key),
+            Err(SasError::CommitmentMismatch)
+        );
+    }
+
+    #[test]
+    fn confirm_produces_an_attestation_that_verifies() {
+        let (manager_a, identity_a) = manager_and_identity();
+        let (manager_b, identity_b) = manager_and_identity();
+
+        let mut sas_a = manager_a.start_verification(&identity_a.user_id, &identity_b.user_id);
+        let mut sas_b = manager_b.start_verification(&identity_b.user_id, &identity_a.user_id);
+
+        sas_a.receive_peer_commitment(sas_b.local_commitment());
+        sas_b.receive_peer_commitment(sas_a.local_commitment());
+        sas_a.reveal_peer_ephemeral_key(sas_b.local_ephemeral_public()).unwrap();
+        sas_b.reveal_peer_ephemeral_key(sas_a.local_ephemeral_public()).unwrap();
+
+        let attestation = sas_a
+            .confirm(&manager_a, &identity_a, &identity_b.public_key)
+            .unwrap();
+
+        assert!(verify_attestation(&attestation, &identity_a.public_key).unwrap());
+    }
+
+    #[test]
+    fn confirm_before_emoji_are_derived_is_rejected() {
+        let (manager_a, identity_a) = manager_and_identity();
+        let (_manager_b, identity_b) = manager_and_identity();
+
+        let sas_a = manager_a.start_verification(&identity_a.user_id, &identity_b.user_id);
+        assert_eq!(
+            sas_a.confirm(&manager_a, &identity_a, &identity_b.public_key),
+            Err(SasError::NotReady)
+        );
+    }
+}
@@ -0,0 +1,353 @@
+// Certificate-style license chain: vendor root -> intermediate -> ephemeral.
+//
+// A flat, vendor-signed `LicenseKey` (see `super`) works for one-off end-user
+// grants, but it means every rotation or revocation requires reissuing keys
+// directly with the root secret. This module adds a TeamSpeak-style chain
+// instead: the vendor root signs a long-lived `IntermediateLicense` that
+// hands signing authority to a reseller or feature-rotation service, which
+// in turn signs short-lived `EphemeralLicense` blocks carrying the actual
+// `LicenseFeatures`. Verifying a chain walks root -> intermediate ->
+// ephemeral, checking each signature and that every child's validity
+// window is fully contained in its parent's, so a compromised or expired
+// intermediate can't be used to mint a leaf that outlives it.
+
+use chrono::Utc;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
+use serde::{Deserialize, Serialize};
+
+use super::{LicenseFeatures, LicenseType, VENDOR_PUBLIC_KEY};
+
+/// Vendor-signed grant of signing authority to an intermediate key, for a
+/// bounded window. Revoking a reseller is as simple as letting this expire
+/// or excluding it from future issuance; it does not touch any ephemeral
+/// license already handed to an end user.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IntermediateLicense {
+    pub issuer_id: String,
+    pub intermediate_public_key: [u8; 32],
+    pub valid_from: i64,
+    pub valid_until: i64,
+    /// Vendor root signature over the canonical encoding of every other
+    /// field.
+    pub signature: Vec<u8>,
+}
+
+/// Intermediate-signed grant of the actual feature set, for a bounded
+/// window nested inside its issuing intermediate's window. This is the
+/// leaf end users carry.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EphemeralLicense {
+    pub license_type: LicenseType,
+    pub features: LicenseFeatures,
+    pub valid_from: i64,
+    pub valid_until: i64,
+    /// Intermediate signature over the canonical encoding of every other
+    /// field.
+    pub signature: Vec<u8>,
+}
+
+/// A complete chain from the vendor root down to the leaf a client holds.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LicenseChain {
+    pub intermediate: IntermediateLicense,
+    pub ephemeral: EphemeralLicense,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChainError {
+    Malformed(String),
+    BadSignature,
+    /// A child's validity window isn't fully contained in its parent's.
+    Bounds,
+    Expired { expired_at: i64 },
+}
+
+impl std::fmt::Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChainError::Malformed(msg) => write!(f, "malformed license chain: {}", msg),
+            ChainError::BadSignature => write!(f, "license chain signature does not verify"),
+            ChainError::Bounds => {
+                write!(f, "ephemeral license's validity window exceeds its intermediate's")
+            }
+            ChainError::Expired { expired_at } => write!(f, "license chain expired at {}", expired_at),
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+#[derive(Serialize)]
+struct IntermediateSignable<'a> {
+    issuer_id: &'a str,
+    intermediate_public_key: [u8; 32],
+    valid_from: i64,
+    valid_until: i64,
+}
+
+impl<'a> From<&'a IntermediateLicense> for IntermediateSignable<'a> {
+    fn from(intermediate: &'a IntermediateLicense) -> Self {
+        Self {
+            issuer_id: &intermediate.issuer_id,
+            intermediate_public_key: intermediate.intermediate_public_key,
+            valid_from: intermediate.valid_from,
+            valid_until: intermediate.valid_until,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EphemeralSignable<'a> {
+    license_type: &'a LicenseType,
+    features: &'a LicenseFeatures,
+    valid_from: i64,
+    valid_until: i64,
+}
+
+impl<'a> From<&'a EphemeralLicense> for EphemeralSignable<'a> {
+    fn from(ephemeral: &'a EphemeralLicense) -> Self {
+        Self {
+            license_type: &ephemeral.license_type,
+            features: &ephemeral.features,
+            valid_from: ephemeral.valid_from,
+            valid_until: ephemeral.valid_until,
+        }
+    }
+}
+
+fn canonical_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>, ChainError> {
+    serde_json::to_vec(value).map_err(|e| ChainError::Malformed(e.to_string()))
+}
+
+fn verify_detached(
+    public_key_bytes: &[u8; 32],
+    message: &[u8],
+    signature_bytes: &[u8],
+) -> Result<(), ChainError> {
+    let public_key =
+        PublicKey::from_bytes(public_key_bytes).map_err(|e| ChainError::Malformed(e.to_string()))?;
+    let signature =
+        Signature::from_bytes(signature_bytes).map_err(|_| ChainError::BadSignature)?;
+    public_key
+        .verify(message, &signature)
+        .map_err(|_| ChainError::BadSignature)
+}
+
+/// Issue a new intermediate license, signed by the vendor root key.
+/// `vendor_secret` never ships with the app; this is for an offline
+/// issuing tool, not `LicenseManager` running on a customer's machine.
+pub fn issue_intermediate(
+    vendor_secret: &[u8; 32],
+    issuer_id: String,
+    intermediate_public_key: [u8; 32],
+    valid_from: i64,
+    valid_until: i64,
+) -> Result<IntermediateLicense, ChainError> {
+    let secret = SecretKey::from_bytes(vendor_secret).map_err(|e| ChainError::Malformed(e.to_string()))?;
+    let public = PublicKey::from(&secret);
+    let keypair = Keypair { secret, public };
+
+    let mut intermediate = IntermediateLicense {
+        issuer_id,
+        intermediate_public_key,
+        valid_from,
+        valid_until,
+        signature: Vec::new(),
+    };
+    let message = canonical_bytes(&IntermediateSignable::from(&intermediate))?;
+    intermediate.signature = keypair.sign(&message).to_bytes().to_vec();
+    Ok(intermediate)
+}
+
+/// Issue a new ephemeral (leaf) license, signed by an intermediate's
+/// secret key. `intermediate_secret` is held by the reseller or rotation
+/// service that owns the matching `IntermediateLicense`.
+pub fn issue_ephemeral(
+    intermediate_secret: &[u8; 32],
+    license_type: LicenseType,
+    features: LicenseFeatures,
+    valid_from: i64,
+    valid_until: i64,
+) -> Result<EphemeralLicense, ChainError> {
+    let secret =
+        SecretKey::from_bytes(intermediate_secret).map_err(|e| ChainError::Malformed(e.to_string()))?;
+    let public = PublicKey::from(&secret);
+    let keypair = Keypair { secret, public };
+
+    let mut ephemeral = EphemeralLicense {
+        license_type,
+        features,
+        valid_from,
+        valid_until,
+        signature: Vec::new(),
+    };
+    let message = canonical_bytes(&EphemeralSignable::from(&ephemeral))?;
+    ephemeral.signature = keypair.sign(&message).to_bytes().to_vec();
+    Ok(ephemeral)
+}
+
+/// Verify a full chain: the intermediate's signature against the compiled-in
+/// vendor root key, the ephemeral's signature against the intermediate's
+/// key, the bounds invariant (`ephemeral` window fully inside
+/// `intermediate` window), and that `now` falls inside the ephemeral's
+/// window. Checks run in that order, so the first failure reported is the
+/// most fundamental one.
+pub fn verify_license_chain(chain: &LicenseChain, now: i64) -> Result<LicenseFeatures, ChainError> {
+    let intermediate_message = canonical_bytes(&IntermediateSignable::from(&chain.intermediate))?;
+    verify_detached(&VENDOR_PUBLIC_KEY, &intermediate_message, &chain.intermediate.signature)?;
+
+    let ephemeral_message = canonical_bytes(&EphemeralSignable::from(&chain.ephemeral))?;
+    verify_detached(
+        &chain.intermediate.intermediate_public_key,
+        &ephemeral_message,
+        &chain.ephemeral.signature,
+    )?;
+
+    if chain.ephemeral.valid_from < chain.intermediate.valid_from
+        || chain.ephemeral.valid_until > chain.intermediate.valid_until
+    {
+        return Err(ChainError::Bounds);
+    }
+
+    if now < chain.ephemeral.valid_from || now > chain.ephemeral.valid_until {
+        return Err(ChainError::Expired {
+            expired_at: chain.ephemeral.valid_until,
+        });
+    }
+
+    Ok(chain.ephemeral.features.clone())
+}
+
+/// Convenience wrapper over [`verify_license_chain`] using the current
+/// wall-clock time.
+pub fn verify_license_chain_now(chain: &LicenseChain) -> Result<LicenseFeatures, ChainError> {
+    verify_license_chain(chain, Utc::now().timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test-only vendor secret key whose public half matches
+    /// `VENDOR_PUBLIC_KEY`. The real secret never lives in this repo.
+    const TEST_VENDOR_SECRET_KEY: [u8; 32] = [
+        81, 6, 29, 11, 55, 185, 219, 21, 59, 62, 40, 11, 29, 235, 249, 49, 14, 7, 27, 215, 224,
+        94, 21, 23, 134, 129, 93, 22, 138, 151, 245, 236,
+    ];
+
+    const OTHER_SECRET_KEY: [u8; 32] = [
+        159, 19, 152, 63, 95, 74, 250, 88, 109, 232, 15, 23, 63, 212, 142, 96, 192, 188, 63, 11,
+        109, 247, 135, 74, 218, 176, 7, 89, 131, 10, 68, 232,
+    ];
+
+    const TEST_INTERMEDIATE_SECRET_KEY: [u8; 32] = [
+        12, 222, 4, 77, 99, 201, 33, 88, 150, 61, 7, 19, 240, 56, 182, 101, 9, 213, 44, 178, 65,
+        120, 3, 251, 67, 198, 14, 90, 205, 172, 31, 16,
+    ];
+
+    fn public_bytes_for(secret_bytes: &[u8; 32]) -> [u8; 32] {
+        let secret = SecretKey::from_bytes(secret_bytes).unwrap();
+        PublicKey::from(&secret).to_bytes()
+    }
+
+    fn sample_chain(ephemeral_from: i64, ephemeral_until: i64) -> LicenseChain {
+        let intermediate_public_key = public_bytes_for(&TEST_INTERMEDIATE_SECRET_KEY);
+        let intermediate = issue_intermediate(
+            &TEST_VENDOR_SECRET_KEY,
+            "reseller-acme".to_string(),
+            intermediate_public_key,
+            1_000_000_000,
+            2_000_000_000,
+        )
+        .unwrap();
+
+        let ephemeral = issue_ephemeral(
+            &TEST_INTERMEDIATE_SECRET_KEY,
+            LicenseType::Professional,
+            LicenseFeatures::professional(),
+            ephemeral_from,
+            ephemeral_until,
+        )
+        .unwrap();
+
+        LicenseChain { intermediate, ephemeral }
+    }
+
+    #[test]
+    fn verifies_a_well_formed_chain() {
+        let chain = sample_chain(1_100_000_000, 1_200_000_000);
+        let features = verify_license_chain(&chain, 1_150_000_000).unwrap();
+        assert_eq!(features.max_connections, LicenseFeatures::professional().max_connections);
+    }
+
+    #[test]
+    fn rejects_an_ephemeral_window_wider_than_its_intermediate() {
+        // valid_until (2_100_000_000) exceeds the intermediate's (2_000_000_000).
+        let chain = sample_chain(1_100_000_000, 2_100_000_000);
+        assert_eq!(
+            verify_license_chain(&chain, 1_150_000_000),
+            Err(ChainError::Bounds)
+        );
+    }
+
+    #[test]
+    fn rejects_an_ephemeral_window_starting_before_its_intermediate() {
+        // valid_from (900_000_000) precedes the intermediate's (1_000_000_000).
+        let chain = sample_chain(900_000_000, 1_200_000_000);
+        assert_eq!(
+            verify_license_chain(&chain, 1_150_000_000),
+            Err(ChainError::Bounds)
+        );
+    }
+
+    #[test]
+    fn rejects_a_timestamp_outside_the_ephemeral_window() {
+        let chain = sample_chain(1_100_000_000, 1_200_000_000);
+        assert_eq!(
+            verify_license_chain(&chain, 1_900_000_000),
+            Err(ChainError::Expired { expired_at: 1_200_000_000 })
+        );
+    }
+
+    #[test]
+    fn rejects_an_intermediate_not_signed_by_the_vendor_root() {
+        let intermediate_public_key = public_bytes_for(&TEST_INTERMEDIATE_SECRET_KEY);
+        let intermediate = issue_intermediate(
+            &OTHER_SECRET_KEY,
+            "reseller-acme".to_string(),
+            intermediate_public_key,
+            1_000_000_000,
+            2_000_000_000,
+        )
+        .unwrap();
+        let ephemeral = issue_ephemeral(
+            &TEST_INTERMEDIATE_SECRET_KEY,
+            LicenseType::Professional,
+            LicenseFeatures::professional(),
+            1_100_000_000,
+            1_200_000_000,
+        )
+        .unwrap();
+
+        let chain = LicenseChain { intermediate, ephemeral };
+        assert_eq!(
+            verify_license_chain(&chain, 1_150_000_000),
+            Err(ChainError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn rejects_an_ephemeral_signed_by_a_different_intermediate() {
+        let mut chain = sample_chain(1_100_000_000, 1_200_000_000);
+        let message = canonical_bytes(&EphemeralSignable::from(&chain.ephemeral)).unwrap();
+        let secret = SecretKey::from_bytes(&OTHER_SECRET_KEY).unwrap();
+        let public = PublicKey::from(&secret);
+        let wrong_keypair = Keypair { secret, public };
+        chain.ephemeral.signature = wrong_keypair.sign(&message).to_bytes().to_vec();
+
+        assert_eq!(
+            verify_license_chain(&chain, 1_150_000_000),
+            Err(ChainError::BadSignature)
+        );
+    }
+}
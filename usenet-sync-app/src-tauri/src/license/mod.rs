@@ -3,8 +3,32 @@ use serde::{Deserialize, Serialize};
 use anyhow::{Result, anyhow};
 use keyring::Entry;
 use sha3::{Sha3_256, Digest};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
 use crate::identity::{IdentityManager, ImmutableIdentity};
 
+/// Certificate-style vendor -> intermediate -> ephemeral license chain, for
+/// reseller/feature-rotation scenarios that shouldn't require reissuing a
+/// flat `LicenseKey` per rotation. See `chain` for details.
+mod chain;
+pub use chain::{
+    issue_ephemeral, issue_intermediate, verify_license_chain, verify_license_chain_now,
+    ChainError, EphemeralLicense, IntermediateLicense, LicenseChain,
+};
+
+/// Server-backed activation/revocation enforcement with an offline grace
+/// window. See `server` for details.
+mod server;
+pub use server::{ActivationClaim, HttpLicenseServerClient, LicenseServerClient, ServerLicenseStatus};
+
+/// Vendor root Ed25519 public key compiled into the binary. License keys
+/// are signed offline by whoever issues them, with the matching secret
+/// key; that secret never ships with the app, so a client can verify a
+/// license key's authenticity but can never mint one.
+const VENDOR_PUBLIC_KEY: [u8; 32] = [
+    104, 47, 105, 18, 222, 219, 148, 195, 12, 159, 109, 213, 120, 180, 134, 247, 29, 17, 202, 202,
+    72, 111, 14, 232, 88, 97, 102, 59, 148, 180, 5, 96,
+];
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum LicenseType {
     Trial,
@@ -118,7 +142,11 @@ pub struct License {
     pub expires_at: Option<DateTime<Utc>>,
     pub device_fingerprint: String,
     pub features: LicenseFeatures,
-    pub signature: String,
+    /// Detached Ed25519 signature (by this device's own identity key) over
+    /// a canonical encoding of every other field. Tampering with `features`,
+    /// `expires_at`, `is_active`, or any other field in the OS keyring
+    /// invalidates it.
+    pub signature: Vec<u8>,
     pub is_active: bool,
     pub activation_count: u32,
     pub max_activations: u32,
@@ -131,11 +159,102 @@ pub struct LicenseKey {
     pub duration_days: Option<i64>,
     pub max_activations: u32,
     pub features: LicenseFeatures,
+    /// Unix timestamp the grant becomes valid at.
+    pub valid_from: i64,
+    /// Unix timestamp the grant expires at, if not perpetual.
+    pub valid_until: Option<i64>,
+    /// Detached Ed25519 signature by the vendor root key over a canonical
+    /// encoding of `license_type`, `duration_days`, `max_activations`,
+    /// `features`, `valid_from`, and `valid_until`. `decode_license_key`
+    /// rejects any key where this doesn't verify against
+    /// [`VENDOR_PUBLIC_KEY`], so a hand-edited grant is just a bad
+    /// signature, not a working license.
+    pub signature: Vec<u8>,
+}
+
+/// Canonical, signature-excluding view of a `LicenseKey`'s grant. Signing
+/// and verifying this (rather than the whole `LicenseKey`, which also
+/// carries the random `key` and the `signature` itself) is what lets
+/// `decode_license_key` catch a tampered `features` or `max_activations`
+/// as a bad signature instead of silently trusting it.
+#[derive(Serialize)]
+struct LicenseKeySignable<'a> {
+    license_type: &'a LicenseType,
+    duration_days: Option<i64>,
+    max_activations: u32,
+    features: &'a LicenseFeatures,
+    valid_from: i64,
+    valid_until: Option<i64>,
+}
+
+impl<'a> LicenseKeySignable<'a> {
+    fn for_key(license_key: &'a LicenseKey) -> Self {
+        Self {
+            license_type: &license_key.license_type,
+            duration_days: license_key.duration_days,
+            max_activations: license_key.max_activations,
+            features: &license_key.features,
+            valid_from: license_key.valid_from,
+            valid_until: license_key.valid_until,
+        }
+    }
+}
+
+/// Canonical, signature-excluding view of a `License`'s fields, used as
+/// the message for `sign_license`/`verify_license_signature`.
+#[derive(Serialize)]
+struct LicenseSignable<'a> {
+    license_id: &'a str,
+    user_id: &'a str,
+    license_type: &'a LicenseType,
+    activated_at: DateTime<Utc>,
+    expires_at: Option<DateTime<Utc>>,
+    device_fingerprint: &'a str,
+    features: &'a LicenseFeatures,
+    is_active: bool,
+    activation_count: u32,
+    max_activations: u32,
+}
+
+impl<'a> From<&'a License> for LicenseSignable<'a> {
+    fn from(license: &'a License) -> Self {
+        Self {
+            license_id: &license.license_id,
+            user_id: &license.user_id,
+            license_type: &license.license_type,
+            activated_at: license.activated_at,
+            expires_at: license.expires_at,
+            device_fingerprint: &license.device_fingerprint,
+            features: &license.features,
+            is_active: license.is_active,
+            activation_count: license.activation_count,
+            max_activations: license.max_activations,
+        }
+    }
 }
 
 pub struct LicenseManager {
     identity_manager: IdentityManager,
     keyring_service: String,
+    server_client: Option<Box<dyn LicenseServerClient>>,
+    /// How long `validate_current_license` will keep accepting a license
+    /// without a successful server re-check before forcing re-validation.
+    offline_grace_period: Duration,
+}
+
+/// Cached result of the last successful (or last known) server check, so
+/// `validate_current_license` can enforce an offline grace window instead
+/// of requiring network access on every call.
+#[derive(Debug, Serialize, Deserialize)]
+struct ServerCheckCache {
+    checked_at: DateTime<Utc>,
+    status: CachedServerStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+enum CachedServerStatus {
+    Active,
+    Revoked,
 }
 
 impl LicenseManager {
@@ -143,9 +262,25 @@ impl LicenseManager {
         Self {
             identity_manager,
             keyring_service: "UsenetSync".to_string(),
+            server_client: None,
+            offline_grace_period: Duration::days(14),
         }
     }
-    
+
+    /// Enable server-backed activation enforcement and revocation checks.
+    /// Without this, `LicenseManager` stays fully offline and enforces
+    /// `max_activations` with only the local keyring counters.
+    pub fn with_server_client(mut self, server_client: Box<dyn LicenseServerClient>) -> Self {
+        self.server_client = Some(server_client);
+        self
+    }
+
+    /// Override the default 14-day offline grace period.
+    pub fn with_offline_grace_period(mut self, grace_period: Duration) -> Self {
+        self.offline_grace_period = grace_period;
+        self
+    }
+
     pub fn activate_trial(&mut self) -> Result<License> {
         let identity = self.identity_manager.get_current_identity()?;
         
@@ -160,8 +295,8 @@ impl LicenseManager {
         }
         
         let license_id = self.generate_license_id(&identity.user_id, &LicenseType::Trial);
-        
-        let license = License {
+
+        let mut license = License {
             license_id: license_id.clone(),
             user_id: identity.user_id.clone(),
             license_type: LicenseType::Trial,
@@ -169,12 +304,13 @@ impl LicenseManager {
             expires_at: Some(Utc::now() + Duration::days(30)),
             device_fingerprint: identity.device_fingerprint.clone(),
             features: LicenseFeatures::trial(),
-            signature: self.sign_license(&license_id, &identity.user_id)?,
+            signature: Vec::new(),
             is_active: true,
             activation_count: 1,
             max_activations: 1,
         };
-        
+        license.signature = self.sign_license(&identity, &license)?;
+
         // Store license
         self.store_license(&license)?;
         
@@ -195,17 +331,31 @@ impl LicenseManager {
             return Err(anyhow!("Device verification failed"));
         }
         
-        // Check activation limit
-        let activation_count = self.get_activation_count(&decoded.key)?;
-        if activation_count >= decoded.max_activations {
-            return Err(anyhow!("License activation limit reached"));
-        }
-        
+        // Claim an activation slot. When a server is configured this is an
+        // atomic, cross-machine claim that enforces `max_activations`
+        // server-side; otherwise fall back to the local keyring counter.
+        let activation_count = if let Some(server_client) = &self.server_client {
+            let claim = server_client.claim_activation(
+                license_key,
+                &identity.user_id,
+                &identity.device_fingerprint,
+            )?;
+            self.store_server_check(&identity.user_id, Utc::now(), CachedServerStatus::Active)?;
+            claim.activation_count
+        } else {
+            let activation_count = self.get_activation_count(&decoded.key)?;
+            if activation_count >= decoded.max_activations {
+                return Err(anyhow!("License activation limit reached"));
+            }
+            self.record_activation(&decoded.key, &identity.user_id)?;
+            activation_count + 1
+        };
+
         let license_id = self.generate_license_id(&identity.user_id, &decoded.license_type);
-        
+
         let expires_at = decoded.duration_days.map(|days| Utc::now() + Duration::days(days));
-        
-        let license = License {
+
+        let mut license = License {
             license_id: license_id.clone(),
             user_id: identity.user_id.clone(),
             license_type: decoded.license_type.clone(),
@@ -213,18 +363,16 @@ impl LicenseManager {
             expires_at,
             device_fingerprint: identity.device_fingerprint.clone(),
             features: decoded.features.clone(),
-            signature: self.sign_license(&license_id, &identity.user_id)?,
+            signature: Vec::new(),
             is_active: true,
-            activation_count: activation_count + 1,
+            activation_count,
             max_activations: decoded.max_activations,
         };
-        
+        license.signature = self.sign_license(&identity, &license)?;
+
         // Store license
         self.store_license(&license)?;
-        
-        // Record activation
-        self.record_activation(&decoded.key, &identity.user_id)?;
-        
+
         Ok(license)
     }
     
@@ -255,7 +403,7 @@ impl LicenseManager {
         }
         
         // Verify signature
-        if !self.verify_license_signature(&license)? {
+        if !self.verify_license_signature(&identity, &license)? {
             return Ok((false, None));
         }
         
@@ -263,10 +411,48 @@ impl LicenseManager {
         if !license.is_active {
             return Ok((false, None));
         }
-        
+
+        // Reconcile with the license server, if one is configured, falling
+        // back to the cached last-known status within the offline grace
+        // period when it can't be reached.
+        if let Some(server_client) = &self.server_client {
+            match server_client.check_status(&license.license_id, &identity.user_id) {
+                Ok(ServerLicenseStatus::Active) => {
+                    self.store_server_check(&identity.user_id, Utc::now(), CachedServerStatus::Active)?;
+                }
+                Ok(ServerLicenseStatus::Revoked) => {
+                    self.store_server_check(&identity.user_id, Utc::now(), CachedServerStatus::Revoked)?;
+                    return Ok((false, None));
+                }
+                Err(_) => match self.get_server_check(&identity.user_id) {
+                    Ok(cache) if cache.status == CachedServerStatus::Revoked => {
+                        return Ok((false, None));
+                    }
+                    Ok(cache) if Utc::now() - cache.checked_at <= self.offline_grace_period => {
+                        // Within the offline grace period; keep operating
+                        // on the last known-good server check.
+                    }
+                    _ => return Ok((false, None)),
+                },
+            }
+        }
+
         Ok((true, Some(license)))
     }
-    
+
+    /// Validate a reseller-issued [`LicenseChain`] (vendor -> intermediate
+    /// -> ephemeral) instead of a flat, directly vendor-signed
+    /// [`LicenseKey`]. Rejects a leaf whose validity window was stretched
+    /// beyond what its issuing intermediate was granted, or whose
+    /// intermediate has itself expired or was never signed by the vendor
+    /// root.
+    pub fn validate_license_chain(
+        &self,
+        license_chain: &LicenseChain,
+    ) -> std::result::Result<LicenseFeatures, ChainError> {
+        verify_license_chain_now(license_chain)
+    }
+
     pub fn get_remaining_days(&self, license: &License) -> Option<i64> {
         license.expires_at.map(|expires| {
             let remaining = expires.signed_duration_since(Utc::now());
@@ -279,10 +465,11 @@ impl LicenseManager {
         
         // Get current license
         let mut license = self.get_stored_license(&identity.user_id)?;
-        
+
         // Mark as inactive
         license.is_active = false;
-        
+        license.signature = self.sign_license(&identity, &license)?;
+
         // Update stored license
         self.store_license(&license)?;
         
@@ -298,29 +485,42 @@ impl LicenseManager {
         format!("LIC-{}", hex::encode(&hasher.finalize()[..12]))
     }
     
-    fn sign_license(&self, license_id: &str, user_id: &str) -> Result<String> {
-        let mut hasher = Sha3_256::new();
-        hasher.update(license_id.as_bytes());
-        hasher.update(user_id.as_bytes());
-        hasher.update(b"UsenetSync-License-v1");
-        
-        Ok(hex::encode(hasher.finalize()))
+    /// Sign `license` with this device's own identity key over a canonical
+    /// encoding of every field but `signature` itself. This is a detached
+    /// signature, not a shared-secret hash, so tampering with the stored
+    /// JSON requires forging a signature under the device's private key
+    /// rather than just recomputing a public hash.
+    fn sign_license(&self, identity: &ImmutableIdentity, license: &License) -> Result<Vec<u8>> {
+        let message = serde_json::to_vec(&LicenseSignable::from(license))?;
+        self.identity_manager.sign_data(identity, &message)
     }
-    
-    fn verify_license_signature(&self, license: &License) -> Result<bool> {
-        let expected = self.sign_license(&license.license_id, &license.user_id)?;
-        Ok(license.signature == expected)
+
+    fn verify_license_signature(&self, identity: &ImmutableIdentity, license: &License) -> Result<bool> {
+        let message = serde_json::to_vec(&LicenseSignable::from(license))?;
+        self.identity_manager
+            .verify_signature(identity, &message, &license.signature)
     }
-    
+
     fn decode_license_key(&self, key: &str) -> Result<LicenseKey> {
         // Format: BASE64(JSON(LicenseKey))
         let decoded = base64::decode(key)?;
         let license_key: LicenseKey = serde_json::from_slice(&decoded)?;
-        
+
         // Validate key format
         if license_key.key.len() < 32 {
             return Err(anyhow!("Invalid license key format"));
         }
+
+        // Verify the vendor actually issued this grant. Without this, a
+        // hand-crafted LicenseKey JSON claiming e.g. enterprise features
+        // would decode and activate just fine.
+        let vendor_key = PublicKey::from_bytes(&VENDOR_PUBLIC_KEY)?;
+        let signature = Signature::from_bytes(&license_key.signature)
+            .map_err(|_| anyhow!("License key signature is malformed"))?;
+        let message = serde_json::to_vec(&LicenseKeySignable::for_key(&license_key))?;
+        vendor_key
+            .verify(&message, &signature)
+            .map_err(|_| anyhow!("License key failed vendor signature verification"))?;
         
         Ok(license_key)
     }
@@ -337,7 +537,25 @@ impl LicenseManager {
         let license: License = serde_json::from_str(&license_json)?;
         Ok(license)
     }
-    
+
+    fn store_server_check(
+        &self,
+        user_id: &str,
+        checked_at: DateTime<Utc>,
+        status: CachedServerStatus,
+    ) -> Result<()> {
+        let entry = Entry::new(&self.keyring_service, &format!("server_check_{}", user_id))?;
+        let cache = ServerCheckCache { checked_at, status };
+        entry.set_password(&serde_json::to_string(&cache)?)?;
+        Ok(())
+    }
+
+    fn get_server_check(&self, user_id: &str) -> Result<ServerCheckCache> {
+        let entry = Entry::new(&self.keyring_service, &format!("server_check_{}", user_id))?;
+        let cache_json = entry.get_password()?;
+        Ok(serde_json::from_str(&cache_json)?)
+    }
+
     fn has_used_trial(&self, user_id: &str) -> Result<bool> {
         let entry = Entry::new(&self.keyring_service, &format!("trial_used_{}", user_id))?;
         match entry.get_password() {
@@ -375,11 +593,22 @@ impl LicenseManager {
         Ok(())
     }
     
-    pub fn generate_license_key(&self, license_type: LicenseType, duration_days: Option<i64>, max_activations: u32) -> Result<String> {
-        // Generate a new license key (for admin use)
+    /// Issue a new license key (admin/issuer use only).
+    ///
+    /// `vendor_secret` is the Ed25519 secret key matching
+    /// [`VENDOR_PUBLIC_KEY`]. It never ships with the app, so in practice
+    /// this is called from an offline issuing tool that holds it, not from
+    /// a `LicenseManager` running on a customer's machine.
+    pub fn generate_license_key(
+        &self,
+        vendor_secret: &[u8; 32],
+        license_type: LicenseType,
+        duration_days: Option<i64>,
+        max_activations: u32,
+    ) -> Result<String> {
         let mut key_bytes = vec![0u8; 32];
         rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut key_bytes);
-        
+
         let features = match license_type {
             LicenseType::Trial => LicenseFeatures::trial(),
             LicenseType::Personal => LicenseFeatures::personal(),
@@ -387,15 +616,36 @@ impl LicenseManager {
             LicenseType::Enterprise => LicenseFeatures::enterprise(),
             LicenseType::Lifetime => LicenseFeatures::lifetime(),
         };
-        
+
+        let valid_from = Utc::now().timestamp();
+        let valid_until = duration_days.map(|days| valid_from + days * 86_400);
+
+        let secret = SecretKey::from_bytes(vendor_secret)?;
+        let public = PublicKey::from(&secret);
+        let keypair = Keypair { secret, public };
+
+        let signable = LicenseKeySignable {
+            license_type: &license_type,
+            duration_days,
+            max_activations,
+            features: &features,
+            valid_from,
+            valid_until,
+        };
+        let message = serde_json::to_vec(&signable)?;
+        let signature = keypair.sign(&message).to_bytes().to_vec();
+
         let license_key = LicenseKey {
             key: hex::encode(&key_bytes),
             license_type,
             duration_days,
             max_activations,
             features,
+            valid_from,
+            valid_until,
+            signature,
         };
-        
+
         Ok(base64::encode(serde_json::to_string(&license_key)?))
     }
 }
@@ -403,17 +653,159 @@ impl LicenseManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
+    /// Test-only vendor secret key whose public half matches
+    /// `VENDOR_PUBLIC_KEY`. The real secret never lives in this repo.
+    const TEST_VENDOR_SECRET_KEY: [u8; 32] = [
+        81, 6, 29, 11, 55, 185, 219, 21, 59, 62, 40, 11, 29, 235, 249, 49, 14, 7, 27, 215, 224,
+        94, 21, 23, 134, 129, 93, 22, 138, 151, 245, 236,
+    ];
+
+    const OTHER_SECRET_KEY: [u8; 32] = [
+        159, 19, 152, 63, 95, 74, 250, 88, 109, 232, 15, 23, 63, 212, 142, 96, 192, 188, 63, 11,
+        109, 247, 135, 74, 218, 176, 7, 89, 131, 10, 68, 232,
+    ];
+
     #[test]
     fn test_license_features() {
         let trial = LicenseFeatures::trial();
         assert_eq!(trial.max_storage_gb, Some(10));
         assert_eq!(trial.max_connections, 2);
         assert!(!trial.private_shares);
-        
+
         let enterprise = LicenseFeatures::enterprise();
         assert_eq!(enterprise.max_storage_gb, None); // Unlimited
         assert_eq!(enterprise.max_connections, 60);
         assert!(enterprise.private_shares);
     }
+
+    #[test]
+    fn decodes_a_vendor_signed_license_key() {
+        let manager = LicenseManager::new(IdentityManager::new());
+        let encoded = manager
+            .generate_license_key(&TEST_VENDOR_SECRET_KEY, LicenseType::Professional, Some(365), 3)
+            .unwrap();
+
+        let decoded = manager.decode_license_key(&encoded).unwrap();
+        assert_eq!(decoded.license_type, LicenseType::Professional);
+        assert_eq!(decoded.max_activations, 3);
+    }
+
+    #[test]
+    fn rejects_a_license_key_with_tampered_features() {
+        let manager = LicenseManager::new(IdentityManager::new());
+        let encoded = manager
+            .generate_license_key(&TEST_VENDOR_SECRET_KEY, LicenseType::Personal, Some(30), 1)
+            .unwrap();
+
+        let mut license_key: LicenseKey =
+            serde_json::from_slice(&base64::decode(&encoded).unwrap()).unwrap();
+        license_key.features = LicenseFeatures::enterprise();
+        let tampered = base64::encode(serde_json::to_string(&license_key).unwrap());
+
+        assert!(manager.decode_license_key(&tampered).is_err());
+    }
+
+    #[test]
+    fn rejects_a_license_key_signed_by_the_wrong_key() {
+        let manager = LicenseManager::new(IdentityManager::new());
+        let encoded = manager
+            .generate_license_key(&OTHER_SECRET_KEY, LicenseType::Enterprise, None, 1)
+            .unwrap();
+
+        assert!(manager.decode_license_key(&encoded).is_err());
+    }
+
+    /// Scripted [`LicenseServerClient`] double: `claim_activation` always
+    /// succeeds, and `check_status` replies with each of `responses` in
+    /// order (falling back to `Active` once exhausted) so a test can drive
+    /// a sequence of server states across several `validate_current_license`
+    /// calls.
+    struct ScriptedServerClient {
+        responses: std::sync::Mutex<std::collections::VecDeque<Result<ServerLicenseStatus>>>,
+    }
+
+    impl ScriptedServerClient {
+        fn new(responses: Vec<Result<ServerLicenseStatus>>) -> Self {
+            Self {
+                responses: std::sync::Mutex::new(responses.into_iter().collect()),
+            }
+        }
+    }
+
+    impl LicenseServerClient for ScriptedServerClient {
+        fn claim_activation(
+            &self,
+            _license_key: &str,
+            _user_id: &str,
+            _device_fingerprint: &str,
+        ) -> Result<ActivationClaim> {
+            Ok(ActivationClaim {
+                activation_id: "act-test".to_string(),
+                activation_count: 1,
+            })
+        }
+
+        fn check_status(&self, _license_id: &str, _user_id: &str) -> Result<ServerLicenseStatus> {
+            match self.responses.lock().unwrap().pop_front() {
+                Some(response) => response,
+                None => Ok(ServerLicenseStatus::Active),
+            }
+        }
+    }
+
+    #[test]
+    fn a_revoked_server_status_invalidates_an_otherwise_valid_license() {
+        let mut manager = LicenseManager::new(IdentityManager::new()).with_server_client(Box::new(
+            ScriptedServerClient::new(vec![Ok(ServerLicenseStatus::Revoked)]),
+        ));
+        let encoded = manager
+            .generate_license_key(&TEST_VENDOR_SECRET_KEY, LicenseType::Professional, Some(30), 5)
+            .unwrap();
+        manager.activate_paid_license(&encoded).unwrap();
+
+        let (valid, license) = manager.validate_current_license().unwrap();
+        assert!(!valid);
+        assert!(license.is_none());
+    }
+
+    #[test]
+    fn an_unreachable_server_falls_back_to_the_last_known_good_check_within_the_grace_period() {
+        let mut manager = LicenseManager::new(IdentityManager::new()).with_server_client(Box::new(
+            ScriptedServerClient::new(vec![
+                Ok(ServerLicenseStatus::Active),
+                Err(anyhow!("connection refused")),
+            ]),
+        ));
+        let encoded = manager
+            .generate_license_key(&TEST_VENDOR_SECRET_KEY, LicenseType::Professional, Some(30), 5)
+            .unwrap();
+        manager.activate_paid_license(&encoded).unwrap();
+
+        // First check succeeds and caches `Active`; the second can't reach
+        // the server at all but should still validate from that cache.
+        assert!(manager.validate_current_license().unwrap().0);
+        let (valid, license) = manager.validate_current_license().unwrap();
+        assert!(valid);
+        assert!(license.is_some());
+    }
+
+    #[test]
+    fn an_unreachable_server_fails_closed_once_the_grace_period_has_passed() {
+        let mut manager = LicenseManager::new(IdentityManager::new())
+            .with_server_client(Box::new(ScriptedServerClient::new(vec![
+                Ok(ServerLicenseStatus::Active),
+                Err(anyhow!("connection refused")),
+            ])))
+            .with_offline_grace_period(Duration::zero());
+        let encoded = manager
+            .generate_license_key(&TEST_VENDOR_SECRET_KEY, LicenseType::Professional, Some(30), 5)
+            .unwrap();
+        manager.activate_paid_license(&encoded).unwrap();
+
+        assert!(manager.validate_current_license().unwrap().0);
+        let (valid, license) = manager.validate_current_license().unwrap();
+        assert!(!valid);
+        assert!(license.is_none());
+    }
 }
\ No newline at end of file
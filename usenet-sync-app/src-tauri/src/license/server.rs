@@ -0,0 +1,124 @@
+// Server-backed activation enforcement with an offline grace period.
+//
+// `get_activation_count`/`record_activation` are purely local keyring
+// counters: nothing stops a user from clearing their own keyring to
+// reset them, and a revoked license can never be pushed back down to a
+// client that never talks to anything. This module adds a thin
+// `LicenseServerClient` trait that `LicenseManager` calls to claim an
+// activation slot atomically server-side and to periodically reconfirm
+// a license is still active, while still tolerating real offline use: a
+// successful check is cached with its timestamp, and operation continues
+// within a configurable grace window (Databend's license manager takes
+// the same approach of separating issuance from runtime enforcement).
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// What the license server reports about a previously activated license.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerLicenseStatus {
+    Active,
+    Revoked,
+}
+
+/// The result of atomically claiming an activation slot.
+#[derive(Debug, Clone)]
+pub struct ActivationClaim {
+    pub activation_id: String,
+    pub activation_count: u32,
+}
+
+/// Talks to whatever system actually owns cross-machine activation state
+/// and revocation. `LicenseManager` falls back to an offline grace window
+/// when this is unreachable, rather than failing closed on every network
+/// blip.
+pub trait LicenseServerClient: Send + Sync {
+    /// Atomically claim one activation slot for `license_key` on
+    /// `device_fingerprint`, enforcing `max_activations` server-side
+    /// (rather than against a purely local counter).
+    fn claim_activation(
+        &self,
+        license_key: &str,
+        user_id: &str,
+        device_fingerprint: &str,
+    ) -> Result<ActivationClaim>;
+
+    /// Confirm a previously activated license is still active and not
+    /// revoked.
+    fn check_status(&self, license_id: &str, user_id: &str) -> Result<ServerLicenseStatus>;
+}
+
+#[derive(Serialize)]
+struct ClaimActivationRequest<'a> {
+    license_key: &'a str,
+    user_id: &'a str,
+    device_fingerprint: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ClaimActivationResponse {
+    activation_id: String,
+    activation_count: u32,
+}
+
+#[derive(Deserialize)]
+struct StatusResponse {
+    status: String,
+}
+
+/// A [`LicenseServerClient`] over a plain HTTPS JSON API.
+pub struct HttpLicenseServerClient {
+    base_url: String,
+    http: reqwest::blocking::Client,
+}
+
+impl HttpLicenseServerClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            http: reqwest::blocking::Client::new(),
+        }
+    }
+}
+
+impl LicenseServerClient for HttpLicenseServerClient {
+    fn claim_activation(
+        &self,
+        license_key: &str,
+        user_id: &str,
+        device_fingerprint: &str,
+    ) -> Result<ActivationClaim> {
+        let response: ClaimActivationResponse = self
+            .http
+            .post(format!("{}/v1/activations/claim", self.base_url))
+            .json(&ClaimActivationRequest {
+                license_key,
+                user_id,
+                device_fingerprint,
+            })
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        Ok(ActivationClaim {
+            activation_id: response.activation_id,
+            activation_count: response.activation_count,
+        })
+    }
+
+    fn check_status(&self, license_id: &str, user_id: &str) -> Result<ServerLicenseStatus> {
+        let response: StatusResponse = self
+            .http
+            .get(format!("{}/v1/licenses/{}/status", self.base_url, license_id))
+            .query(&[("user_id", user_id)])
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        match response.status.as_str() {
+            "active" => Ok(ServerLicenseStatus::Active),
+            "revoked" => Ok(ServerLicenseStatus::Revoked),
+            other => Err(anyhow!("unrecognized license status from server: {}", other)),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// License-tier capability ACL.
+//
+// `LicenseStatus`/`LicenseFeatures`/`tier` were being fetched for display
+// purposes only -- nothing actually stopped a trial or basic-tier caller
+// from invoking a paid-tier-only command, or from exceeding a numeric
+// limit like `max_shares`. This module is the single table of which
+// command requires which tier and limit, and the single gate every
+// license-sensitive command is checked against before it reaches the
+// backend, so a denial happens up front with a structured reason instead
+// of failing (or silently succeeding) mid-flight.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+use crate::{LicenseFeatures, LicenseStatus};
+
+/// License tiers from least to most capable. A trial license is treated as
+/// one rung below `"basic"` regardless of the underlying SKU's `tier`
+/// field, since a trial is bounded by the same numeric limits as basic
+/// plus the tier gate itself.
+const TIER_ORDER: &[&str] = &["trial", "basic", "pro", "enterprise"];
+
+/// A numeric limit a command's request size/count is checked against, read
+/// off the matching field of the caller's [`LicenseFeatures`].
+#[derive(Debug, Clone, Copy)]
+pub enum Limit {
+    MaxFileSize,
+    MaxConnections,
+    MaxShares,
+}
+
+impl Limit {
+    fn allowance(self, features: &LicenseFeatures) -> u64 {
+        match self {
+            Limit::MaxFileSize => features.max_file_size,
+            Limit::MaxConnections => features.max_connections as u64,
+            Limit::MaxShares => features.max_shares as u64,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Limit::MaxFileSize => "max_file_size",
+            Limit::MaxConnections => "max_connections",
+            Limit::MaxShares => "max_shares",
+        }
+    }
+}
+
+/// The tier and (optional) numeric limit a command requires of the
+/// caller's license.
+#[derive(Debug, Clone, Copy)]
+pub struct CommandRequirement {
+    pub min_tier: &'static str,
+    pub limit: Option<Limit>,
+}
+
+static COMMAND_REQUIREMENTS: Lazy<HashMap<&'static str, CommandRequirement>> = Lazy::new(|| {
+    let mut table = HashMap::new();
+    table.insert(
+        "create_share",
+        CommandRequirement { min_tier: "basic", limit: Some(Limit::MaxShares) },
+    );
+    table.insert("download_share", CommandRequirement { min_tier: "basic", limit: None });
+    table.insert("upload_folder", CommandRequirement { min_tier: "basic", limit: None });
+    table.insert(
+        "publish_folder",
+        CommandRequirement { min_tier: "pro", limit: None },
+    );
+    table
+});
+
+/// Returned when a command is invoked by a license that doesn't meet the
+/// command's tier or numeric limit, matching the shape a caller can use to
+/// point the user at an upgrade path.
+#[derive(Debug, Clone)]
+pub struct LicenseDenied {
+    pub command: String,
+    pub required_tier: String,
+    pub limit: Option<String>,
+}
+
+impl std::fmt::Display for LicenseDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.limit {
+            Some(limit) => write!(
+                f,
+                "'{}' requires the '{}' tier and is over its {} limit",
+                self.command, self.required_tier, limit
+            ),
+            None => write!(f, "'{}' requires the '{}' tier", self.command, self.required_tier),
+        }
+    }
+}
+
+impl std::error::Error for LicenseDenied {}
+
+/// A command's requirement, serializable for [`crate::get_command_permissions`]
+/// so the UI can disable locked actions up front.
+#[derive(Debug, serde::Serialize)]
+pub struct CommandPermission {
+    pub command: String,
+    #[serde(rename = "requiredTier")]
+    pub required_tier: String,
+    pub limit: Option<String>,
+}
+
+/// The full command -> requirement table, for the UI to disable locked
+/// actions before the user ever attempts them.
+pub fn command_permissions() -> Vec<CommandPermission> {
+    let mut permissions: Vec<CommandPermission> = COMMAND_REQUIREMENTS
+        .iter()
+        .map(|(command, requirement)| CommandPermission {
+            command: command.to_string(),
+            required_tier: requirement.min_tier.to_string(),
+            limit: requirement.limit.map(|limit| limit.name().to_string()),
+        })
+        .collect();
+    permissions.sort_by(|a, b| a.command.cmp(&b.command));
+    permissions
+}
+
+fn tier_rank(tier: &str) -> usize {
+    // An unrecognized tier string (a stale cache entry, a future tier this
+    // build doesn't know about yet) must fail closed to the most
+    // restrictive rank, not fail open to "basic" -- gating decisions trust
+    // this rank directly.
+    TIER_ORDER.iter().position(|t| *t == tier).unwrap_or(0)
+}
+
+fn caller_tier_rank(status: &LicenseStatus) -> usize {
+    if status.trial {
+        tier_rank("trial")
+    } else {
+        tier_rank(&status.tier)
+    }
+}
+
+/// Check whether `status` is permitted to invoke `command`, optionally
+/// against a `requested` size/count (e.g. the share count the call would
+/// bring the caller to), before the command ever reaches
+/// [`crate::unified_backend`]. A `requested` equal to the limit passes.
+/// Commands with no entry in the table are unrestricted.
+pub fn check_command(command: &str, status: &LicenseStatus, requested: Option<u64>) -> Result<(), LicenseDenied> {
+    let Some(requirement) = COMMAND_REQUIREMENTS.get(command) else {
+        return Ok(());
+    };
+
+    if caller_tier_rank(status) < tier_rank(requirement.min_tier) {
+        return Err(LicenseDenied {
+            command: command.to_string(),
+            required_tier: requirement.min_tier.to_string(),
+            limit: None,
+        });
+    }
+
+    if let (Some(limit), Some(requested)) = (requirement.limit, requested) {
+        if requested > limit.allowance(&status.features) {
+            return Err(LicenseDenied {
+                command: command.to_string(),
+                required_tier: requirement.min_tier.to_string(),
+                limit: Some(limit.name().to_string()),
+            });
+        }
+    }
+
+    Ok(())
+}
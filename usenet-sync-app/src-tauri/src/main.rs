@@ -3,11 +3,15 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::process::Command;
-use std::sync::Mutex;
-use tauri::State;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{FuturesUnordered, StreamExt};
+use tauri::{Emitter, State};
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 
 // Import TurboActivate integration
@@ -16,12 +20,68 @@ use turboactivate::TurboActivate;
 
 // Import commands module
 mod commands;
-use commands::system::init_system_commands;
+use commands::system::{init_system_commands, SystemState};
 
 // Import unified backend integration
 mod unified_backend;
 use unified_backend::execute_unified_command;
 
+// Share code codec for human-typeable share identifiers
+mod share_code;
+
+// Offline-first operation journal for mutating backend commands
+mod operation_log;
+
+// SASL client mechanisms for authenticating backend/NNTP credentials
+mod sasl;
+
+// Offline, signature-based license verification
+mod offline_license;
+
+// Multi-signal, drift-tolerant hardware fingerprinting
+mod hardware_fingerprint;
+
+// Embedded Prometheus metrics endpoint
+mod metrics;
+
+// Read-only WebDAV gateway for published shares
+mod webdav;
+
+// License-tier capability ACL gating unified backend commands
+mod license_acl;
+
+// Headless daemon mode (`--headless --listen ADDR`)
+mod daemon;
+
+// Bounded concurrency and short-TTL caching in front of the unified backend
+mod backend_pool;
+
+// RSS/Atom feed watcher for automatic indexing and download
+mod feeds;
+
+// Encrypted-at-rest server credential storage
+mod credentials;
+
+// Persisted transfer control flags and progress, backing pause/resume/cancel
+mod transfers;
+
+// Background sampler maintaining live system/network/transfer stats
+mod stats_sampler;
+
+// Pluggable database backend selection (SQLite default, PostgreSQL opt-in)
+mod database;
+
+// Optional Discord Rich Presence integration
+mod discord_presence;
+
+// Vendor-signed Ed25519 licensing (flat keys and certificate-style chains),
+// independent of TurboActivate
+mod license;
+
+// Ed25519 device identity: multi-device trust lists, SAS (emoji) device
+// verification, and replay-resistant identity proofs
+mod identity;
+
 // Helper function to get the workspace directory
 fn get_workspace_dir() -> PathBuf {
     std::env::current_dir()
@@ -115,6 +175,51 @@ struct AppState {
     #[allow(dead_code)]
     python_process: Mutex<Option<std::process::Child>>,
     transfers: Mutex<HashMap<String, Transfer>>,
+    webdav_gateway: Mutex<Option<webdav::WebdavGatewayHandle>>,
+    /// The last [`LicenseStatus`] computed by [`check_license`], reused by
+    /// [`execute_unified_command_licensed`] so every gated command doesn't
+    /// re-query TurboActivate.
+    license_status_cache: Mutex<Option<LicenseStatus>>,
+    /// Bounded-concurrency, short-TTL-cached front end for the unified
+    /// backend, shared across calls so `folder_info`-style polling doesn't
+    /// re-pay a full round trip (and repeated login) every time. Already
+    /// internally synchronized (a `Semaphore` plus its own cache lock), so
+    /// unlike the other fields here it isn't wrapped in an outer `Mutex` --
+    /// that would just re-serialize the concurrency it exists to bound.
+    backend_pool: backend_pool::BackendPool,
+    /// Key derived from the user's master passphrase by `set_master_passphrase`
+    /// or `unlock`, kept only in memory for the life of the process -- never
+    /// written to disk. `save_server_config`/`get_server_config` need this
+    /// set before they can touch the encrypted credential file.
+    master_key: Mutex<Option<[u8; 32]>>,
+    /// Set by `toggle_discord_presence`; read once per tick by
+    /// `discord_presence::watch` to decide whether to be connected at all.
+    discord_presence_enabled: Mutex<bool>,
+    /// Vendor-signed Ed25519 license path (`activate_vendor_license_key`,
+    /// `activate_license_chain`), checked by [`apply_vendor_license`] as a
+    /// fallback when TurboActivate itself reports no active license.
+    license_manager: Mutex<license::LicenseManager>,
+    /// A validated license chain from `activate_license_chain`, re-checked
+    /// (not just cached) on every [`apply_vendor_license`] call since a
+    /// chain's ephemeral leaf can expire between checks.
+    license_chain: Mutex<Option<license::LicenseChain>>,
+    /// A signed offline license blob from `activate_offline_license`,
+    /// re-verified (not just cached) on every [`apply_vendor_license`]
+    /// call since it carries its own `expires_at`.
+    offline_license: Mutex<Option<offline_license::SignedLicense>>,
+    /// This machine's fuzzy hardware fingerprint, recorded by
+    /// `activate_license` at activation time and re-checked by
+    /// [`apply_hardware_fingerprint_gate`] on every status check.
+    hardware_fingerprint_reference: Mutex<Option<hardware_fingerprint::HardwareFingerprint>>,
+    /// This device's Ed25519 identity, shared by the multi-device trust
+    /// list and SAS verification commands.
+    identity_manager: Mutex<identity::IdentityManager>,
+    /// Device lists created by `create_device_list`/`add_trusted_device`,
+    /// keyed by the primary device's `user_id`.
+    device_lists: Mutex<HashMap<String, identity::SignedDeviceList>>,
+    /// In-progress SAS verification sessions from `start_sas_verification`,
+    /// keyed by peer user id, pending `confirm_sas_verification`.
+    sas_sessions: Mutex<HashMap<String, identity::Sas>>,
 }
 
 // Type definitions matching TypeScript
@@ -211,7 +316,7 @@ struct SegmentProgress {
     retries: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 struct ServerConfig {
     hostname: String,
     port: u16,
@@ -224,7 +329,7 @@ struct ServerConfig {
     group: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SystemStats {
     #[serde(rename = "cpuUsage")]
     cpu_usage: f32,
@@ -240,41 +345,69 @@ struct SystemStats {
     total_shares: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct NetworkSpeed {
     upload: f64,
     download: f64,
 }
 
+/// Serializable mirror of [`identity::VerificationAttestation`], which
+/// doesn't derive `Serialize`/`Deserialize` itself since it's meant to be
+/// signed/verified in-process, not shipped over Tauri's IPC boundary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SasAttestation {
+    attester_user_id: String,
+    attested_public_key: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl From<identity::VerificationAttestation> for SasAttestation {
+    fn from(attestation: identity::VerificationAttestation) -> Self {
+        Self {
+            attester_user_id: attestation.attester_user_id,
+            attested_public_key: attestation.attested_public_key,
+            signature: attestation.signature,
+        }
+    }
+}
+
 // License Commands
 #[tauri::command]
 async fn activate_license(state: State<'_, AppState>, key: String) -> Result<bool, String> {
     let license = state.license.lock().unwrap();
     match license.activate(&key, None) {
-        Ok(_) => Ok(true),
+        Ok(_) => {
+            // Record this machine's fingerprint now, so later status
+            // checks can tell this license is still running on the
+            // machine it was activated on (see
+            // `apply_hardware_fingerprint_gate`).
+            *state.hardware_fingerprint_reference.lock().unwrap() = Some(hardware_fingerprint::current_fingerprint());
+            Ok(true)
+        }
         Err(e) => Err(e.to_string()),
     }
 }
 
-#[tauri::command]
-async fn check_license(state: State<'_, AppState>) -> Result<LicenseStatus, String> {
-    let license = state.license.lock().unwrap();
-    
+/// Read `license`'s current activation/trial/tier state into a
+/// [`LicenseStatus`], shared by [`check_license`] and
+/// [`execute_unified_command_licensed`] so the ACL check reads the same
+/// data the UI displays.
+fn build_license_status(license: &TurboActivate) -> LicenseStatus {
     let activated = license.is_activated().unwrap_or(false);
     let genuine = license.is_genuine().unwrap_or(false);
     let hardware_id = license.get_hardware_id().unwrap_or_else(|_| "unknown".to_string());
-    
+
     let (trial, trial_days) = if !activated {
         let days = license.get_trial_days_remaining().unwrap_or(0);
         (days > 0, Some(days))
     } else {
         (false, None)
     };
-    
+
     let tier = license.get_feature_value("tier")
         .unwrap_or_else(|_| "basic".to_string());
-    
-    Ok(LicenseStatus {
+
+    LicenseStatus {
         activated,
         genuine,
         trial,
@@ -286,7 +419,442 @@ async fn check_license(state: State<'_, AppState>) -> Result<LicenseStatus, Stri
             max_connections: 30,
             max_shares: 100,
         },
-    })
+    }
+}
+
+/// Map a vendor-signed [`license::LicenseType`]/[`license::LicenseFeatures`]
+/// pair onto this file's own `LicenseStatus`/`LicenseFeatures` vocabulary,
+/// so a license issued through [`license::LicenseManager`] gates commands
+/// through [`license_acl`] exactly like a TurboActivate one does.
+fn vendor_license_to_status(license: &license::License) -> LicenseStatus {
+    let tier = match license.license_type {
+        license::LicenseType::Trial => "trial",
+        license::LicenseType::Personal => "basic",
+        license::LicenseType::Professional => "pro",
+        license::LicenseType::Enterprise | license::LicenseType::Lifetime => "enterprise",
+    };
+
+    LicenseStatus {
+        activated: license.is_active,
+        genuine: true,
+        trial: license.license_type == license::LicenseType::Trial,
+        trial_days: None,
+        hardware_id: license.device_fingerprint.clone(),
+        tier: tier.to_string(),
+        features: LicenseFeatures {
+            max_file_size: license
+                .features
+                .max_storage_gb
+                .map(|gb| gb * 1024 * 1024 * 1024)
+                .unwrap_or(u64::MAX),
+            max_connections: license.features.max_connections,
+            max_shares: license.features.max_folders.unwrap_or(u32::MAX),
+        },
+    }
+}
+
+/// Map a verified license chain's leaf onto `LicenseStatus`, the same way
+/// [`vendor_license_to_status`] does for a flat license key. `hardware_id`
+/// comes from this device's identity, since a chain's ephemeral leaf
+/// doesn't carry a device fingerprint of its own.
+fn vendor_chain_to_status(
+    chain: &license::LicenseChain,
+    features: license::LicenseFeatures,
+    hardware_id: String,
+) -> LicenseStatus {
+    let license_type = chain.ephemeral.license_type.clone();
+    let tier = match license_type {
+        license::LicenseType::Trial => "trial",
+        license::LicenseType::Personal => "basic",
+        license::LicenseType::Professional => "pro",
+        license::LicenseType::Enterprise | license::LicenseType::Lifetime => "enterprise",
+    };
+
+    LicenseStatus {
+        activated: true,
+        genuine: true,
+        trial: license_type == license::LicenseType::Trial,
+        trial_days: None,
+        hardware_id,
+        tier: tier.to_string(),
+        features: LicenseFeatures {
+            max_file_size: features.max_storage_gb.map(|gb| gb * 1024 * 1024 * 1024).unwrap_or(u64::MAX),
+            max_connections: features.max_connections,
+            max_shares: features.max_folders.unwrap_or(u32::MAX),
+        },
+    }
+}
+
+/// Map a verified offline license blob's claims onto `LicenseStatus`. The
+/// blob doesn't carry a full `LicenseFeatures` breakdown (just a tier name
+/// and `max_connections`), so the remaining fields fall back to the same
+/// defaults [`build_license_status`] uses for TurboActivate.
+fn offline_license_to_status(status: turboactivate::LicenseStatus, claims: &offline_license::LicenseClaims) -> LicenseStatus {
+    LicenseStatus {
+        activated: status.activated,
+        genuine: status.genuine,
+        trial: status.trial,
+        trial_days: None,
+        hardware_id: status.hardware_id,
+        tier: status.tier,
+        features: LicenseFeatures {
+            max_file_size: 10 * 1024 * 1024 * 1024,
+            max_connections: claims.max_connections,
+            max_shares: 100,
+        },
+    }
+}
+
+/// If TurboActivate itself reports no active license, fall back in turn
+/// to the vendor-signed Ed25519 license path, a certificate-style license
+/// chain from `activate_license_chain`, and finally a self-contained
+/// offline license blob from `activate_offline_license`. Re-checked (not
+/// cached) every time `status` is built, since each of these enforces its
+/// own expiry/offline-grace window independently of TurboActivate.
+fn apply_vendor_license(status: &mut LicenseStatus, state: &AppState) {
+    if status.activated {
+        return;
+    }
+    let mut license_manager = state.license_manager.lock().unwrap();
+    if let Ok((true, Some(license))) = license_manager.validate_current_license() {
+        *status = vendor_license_to_status(&license);
+        return;
+    }
+    drop(license_manager);
+
+    if let Some(chain) = state.license_chain.lock().unwrap().as_ref() {
+        if let Ok(features) = license::verify_license_chain_now(chain) {
+            let hardware_id = state
+                .identity_manager
+                .lock()
+                .unwrap()
+                .get_current_identity()
+                .map(|identity| identity.device_fingerprint)
+                .unwrap_or_else(|_| "unknown".to_string());
+            *status = vendor_chain_to_status(chain, features, hardware_id);
+            return;
+        }
+    }
+
+    if let Some(blob) = state.offline_license.lock().unwrap().as_ref() {
+        if let Ok(turbo_status) = state.license.lock().unwrap().verify_offline_license(blob) {
+            *status = offline_license_to_status(turbo_status, &blob.claims);
+        }
+    }
+}
+
+/// If a hardware fingerprint was recorded at activation time (see
+/// `activate_license`), require that this machine's current fingerprint
+/// still matches it well enough. Closes the gap where
+/// `hardware_fingerprint::verify_fingerprint`'s M-of-N match was computed
+/// nowhere reachable and so never actually gated a license. A mismatch is
+/// treated the same as no active TurboActivate license, so
+/// `apply_vendor_license` still gets a chance to find one of its own.
+fn apply_hardware_fingerprint_gate(status: &mut LicenseStatus, state: &AppState) {
+    if !status.activated {
+        return;
+    }
+    let reference = state.hardware_fingerprint_reference.lock().unwrap().clone();
+    let Some(reference) = reference else {
+        return;
+    };
+    let config = hardware_fingerprint::FingerprintConfig::default();
+    let result = state.license.lock().unwrap().verify_hardware_fingerprint(&reference, &config);
+    if !result.accepted {
+        status.activated = false;
+        status.genuine = false;
+    }
+}
+
+#[tauri::command]
+async fn check_license(state: State<'_, AppState>) -> Result<LicenseStatus, String> {
+    let mut status = build_license_status(&state.license.lock().unwrap());
+    apply_hardware_fingerprint_gate(&mut status, &state);
+    apply_vendor_license(&mut status, &state);
+    *state.license_status_cache.lock().unwrap() = Some(status.clone());
+    Ok(status)
+}
+
+/// Return the cached [`LicenseStatus`] from the last [`check_license`]
+/// call, computing and caching one fresh if none exists yet.
+fn cached_license_status(state: &AppState) -> LicenseStatus {
+    if let Some(status) = state.license_status_cache.lock().unwrap().clone() {
+        return status;
+    }
+    let mut status = build_license_status(&state.license.lock().unwrap());
+    apply_hardware_fingerprint_gate(&mut status, state);
+    apply_vendor_license(&mut status, state);
+    *state.license_status_cache.lock().unwrap() = Some(status.clone());
+    status
+}
+
+/// Activate a vendor-issued Ed25519 license key (the `license_manager` path,
+/// independent of TurboActivate's `activate_license`). Invalidates the
+/// cached status so the next [`check_license`]/ACL check picks it up.
+#[tauri::command]
+async fn activate_vendor_license_key(state: State<'_, AppState>, key: String) -> Result<bool, String> {
+    state
+        .license_manager
+        .lock()
+        .unwrap()
+        .activate_paid_license(&key)
+        .map_err(|e| e.to_string())?;
+    *state.license_status_cache.lock().unwrap() = None;
+    Ok(true)
+}
+
+/// Activate a self-contained, signed offline license blob (see
+/// `offline_license` for why this exists independently of TurboActivate's
+/// own server-backed activation). Verified in full before being stored,
+/// same as `activate_license_chain`.
+#[tauri::command]
+async fn activate_offline_license(state: State<'_, AppState>, blob_json: String) -> Result<bool, String> {
+    let blob: offline_license::SignedLicense =
+        serde_json::from_str(&blob_json).map_err(|e| format!("invalid license blob: {}", e))?;
+    state.license.lock().unwrap().verify_offline_license(&blob).map_err(|e| e.to_string())?;
+    *state.offline_license.lock().unwrap() = Some(blob);
+    *state.license_status_cache.lock().unwrap() = None;
+    Ok(true)
+}
+
+/// Keyring service name shared by every `UsenetSync` keyring entry,
+/// matching `license::LicenseManager`/`identity::IdentityManager`'s own
+/// `keyring_service`.
+const KEYRING_SERVICE: &str = "UsenetSync";
+
+/// Persist `chain` to the OS keyring, the same way
+/// `license::LicenseManager::store_license` persists a flat license, so
+/// `activate_license_chain` survives an app restart instead of silently
+/// reverting to unlicensed.
+fn store_license_chain(chain: &license::LicenseChain) -> Result<(), String> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, "license_chain").map_err(|e| e.to_string())?;
+    entry
+        .set_password(&serde_json::to_string(chain).map_err(|e| e.to_string())?)
+        .map_err(|e| e.to_string())
+}
+
+/// Load a license chain persisted by [`store_license_chain`], if any was
+/// ever stored (or if it fails to parse, e.g. from an older format).
+fn load_license_chain() -> Option<license::LicenseChain> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, "license_chain").ok()?;
+    serde_json::from_str(&entry.get_password().ok()?).ok()
+}
+
+/// Activate a certificate-style license chain (vendor root -> intermediate
+/// -> ephemeral), as an alternative to a flat vendor license key -- see
+/// `license::chain` for why a reseller/feature-rotation scenario would
+/// issue one of these instead. `chain_json` is verified in full before
+/// being stored, so a malformed or expired chain is rejected up front
+/// rather than surfacing as a confusing ACL denial later. Also persisted
+/// to the keyring so it's still active after the app restarts.
+#[tauri::command]
+async fn activate_license_chain(state: State<'_, AppState>, chain_json: String) -> Result<bool, String> {
+    let chain: license::LicenseChain =
+        serde_json::from_str(&chain_json).map_err(|e| format!("invalid license chain: {}", e))?;
+    license::verify_license_chain_now(&chain).map_err(|e| e.to_string())?;
+    store_license_chain(&chain)?;
+    *state.license_chain.lock().unwrap() = Some(chain);
+    *state.license_status_cache.lock().unwrap() = None;
+    Ok(true)
+}
+
+// Multi-device identity commands
+
+/// Persist every user's device list to the OS keyring, the same way
+/// [`store_license_chain`] persists the license chain, so
+/// `create_device_list`/`add_trusted_device` survive an app restart.
+fn store_device_lists(device_lists: &HashMap<String, identity::SignedDeviceList>) -> Result<(), String> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, "device_lists").map_err(|e| e.to_string())?;
+    entry
+        .set_password(&serde_json::to_string(device_lists).map_err(|e| e.to_string())?)
+        .map_err(|e| e.to_string())
+}
+
+/// Load the device lists persisted by [`store_device_lists`], if any.
+fn load_device_lists() -> HashMap<String, identity::SignedDeviceList> {
+    keyring::Entry::new(KEYRING_SERVICE, "device_lists")
+        .ok()
+        .and_then(|entry| entry.get_password().ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Start a brand-new signed device list containing only this device, and
+/// store it as the one for this user. Replaces any existing list for the
+/// same `user_id` -- re-running this is how a user abandons a previous
+/// device list (e.g. after losing every other device) rather than adding
+/// to it.
+#[tauri::command]
+async fn create_device_list(state: State<'_, AppState>) -> Result<identity::DeviceListPayload, String> {
+    let mut identity_manager = state.identity_manager.lock().unwrap();
+    let primary = identity_manager.get_current_identity().map_err(|e| e.to_string())?;
+    let now = chrono::Utc::now().timestamp();
+    let list = identity::SignedDeviceList::new(&identity_manager, &primary, now).map_err(|e| e.to_string())?;
+    let payload = list.payload().map_err(|e| e.to_string())?;
+    let mut device_lists = state.device_lists.lock().unwrap();
+    device_lists.insert(primary.user_id.clone(), list);
+    store_device_lists(&device_lists)?;
+    Ok(payload)
+}
+
+/// Append a device's public key to this user's device list and re-sign it
+/// as the current primary. `device_id`/`public_key` identify the device
+/// being trusted -- typically exchanged out-of-band first (e.g. via
+/// [`start_sas_verification`]).
+#[tauri::command]
+async fn add_trusted_device(
+    state: State<'_, AppState>,
+    device_id: String,
+    public_key: Vec<u8>,
+) -> Result<identity::DeviceListPayload, String> {
+    let mut identity_manager = state.identity_manager.lock().unwrap();
+    let primary = identity_manager.get_current_identity().map_err(|e| e.to_string())?;
+
+    let mut device_lists = state.device_lists.lock().unwrap();
+    let list = device_lists
+        .get(&primary.user_id)
+        .ok_or_else(|| "no device list exists for this user yet -- call create_device_list first".to_string())?;
+
+    let now = chrono::Utc::now().timestamp();
+    let device = identity::DevicePublicKey { device_id, public_key, added_at: now };
+    let updated = list.add_device(&identity_manager, &primary, device, now).map_err(|e| e.to_string())?;
+    let payload = updated.payload().map_err(|e| e.to_string())?;
+    device_lists.insert(primary.user_id.clone(), updated);
+    store_device_lists(&device_lists)?;
+    Ok(payload)
+}
+
+/// Verify a device list signed by some primary device -- typically one
+/// received from a peer rather than this user's own, e.g. while deciding
+/// whether to trust a contact's device roster. Does not touch local
+/// storage; the caller is responsible for pinning `returned public key`
+/// as the new `trusted_primary_public_key` for the next call.
+#[tauri::command]
+async fn verify_device_list_signature(
+    list: identity::SignedDeviceList,
+    trusted_primary_public_key: Vec<u8>,
+    last_seen_timestamp: Option<i64>,
+    freshness_window_secs: i64,
+) -> Result<(identity::DeviceListPayload, Vec<u8>), String> {
+    let now = chrono::Utc::now().timestamp();
+    identity::verify_device_list(&list, &trusted_primary_public_key, last_seen_timestamp, freshness_window_secs, now)
+        .map_err(|e| e.to_string())
+}
+
+// SAS (emoji) out-of-band device verification commands. A session lives in
+// `AppState::sas_sessions`, keyed by peer user id, across the handshake's
+// commit/reveal/confirm steps.
+
+/// Step 0: start a session with `peer_user_id` and return this device's
+/// commitment, to be sent to the peer before either side reveals its real
+/// ephemeral key.
+#[tauri::command]
+async fn start_sas_verification(state: State<'_, AppState>, peer_user_id: String) -> Result<Vec<u8>, String> {
+    let mut identity_manager = state.identity_manager.lock().unwrap();
+    let local_identity = identity_manager.get_current_identity().map_err(|e| e.to_string())?;
+    let session = identity_manager.start_verification(&local_identity.user_id, &peer_user_id);
+    let commitment = session.local_commitment().to_vec();
+    state.sas_sessions.lock().unwrap().insert(peer_user_id, session);
+    Ok(commitment)
+}
+
+/// Step 1: record the peer's commitment (received out of band) and return
+/// this device's real ephemeral public key, to be sent only now that both
+/// commitments have been exchanged.
+#[tauri::command]
+async fn sas_receive_peer_commitment(
+    state: State<'_, AppState>,
+    peer_user_id: String,
+    peer_commitment: Vec<u8>,
+) -> Result<Vec<u8>, String> {
+    let commitment: [u8; 32] = peer_commitment.try_into().map_err(|_| "commitment must be 32 bytes".to_string())?;
+    let mut sessions = state.sas_sessions.lock().unwrap();
+    let session = sessions
+        .get_mut(&peer_user_id)
+        .ok_or_else(|| "no SAS session for this peer -- call start_sas_verification first".to_string())?;
+    session.receive_peer_commitment(commitment);
+    Ok(session.local_ephemeral_public().to_vec())
+}
+
+/// Step 2: accept the peer's real ephemeral key, check it against its
+/// earlier commitment, and derive the emoji both users should compare out
+/// of band.
+#[tauri::command]
+async fn sas_reveal_peer_ephemeral_key(
+    state: State<'_, AppState>,
+    peer_user_id: String,
+    peer_ephemeral_public: Vec<u8>,
+) -> Result<Vec<String>, String> {
+    let key: [u8; 32] = peer_ephemeral_public.try_into().map_err(|_| "ephemeral key must be 32 bytes".to_string())?;
+    let mut sessions = state.sas_sessions.lock().unwrap();
+    let session = sessions
+        .get_mut(&peer_user_id)
+        .ok_or_else(|| "no SAS session for this peer -- call start_sas_verification first".to_string())?;
+    session.reveal_peer_ephemeral_key(key).map_err(|e| e.to_string())?;
+    Ok(session.emoji().map_err(|e| e.to_string())?.iter().map(|e| e.to_string()).collect())
+}
+
+/// Step 3: once the displayed emoji have been confirmed to match out of
+/// band, sign the peer's long-term identity key and return a portable
+/// attestation. Ends the session.
+#[tauri::command]
+async fn confirm_sas_verification(
+    state: State<'_, AppState>,
+    peer_user_id: String,
+    peer_long_term_public_key: Vec<u8>,
+) -> Result<SasAttestation, String> {
+    let mut identity_manager = state.identity_manager.lock().unwrap();
+    let local_identity = identity_manager.get_current_identity().map_err(|e| e.to_string())?;
+    let mut sessions = state.sas_sessions.lock().unwrap();
+    let session = sessions
+        .remove(&peer_user_id)
+        .ok_or_else(|| "no SAS session for this peer -- call start_sas_verification first".to_string())?;
+    session
+        .confirm(&identity_manager, &local_identity, &peer_long_term_public_key)
+        .map(SasAttestation::from)
+        .map_err(|e| e.to_string())
+}
+
+/// Check a [`SasAttestation`] against the attester's long-term Ed25519
+/// public key -- e.g. one already trusted from a prior device list.
+#[tauri::command]
+async fn verify_sas_attestation(attestation: SasAttestation, attester_public_key: Vec<u8>) -> Result<bool, String> {
+    let attestation = identity::VerificationAttestation {
+        attester_user_id: attestation.attester_user_id,
+        attested_public_key: attestation.attested_public_key,
+        signature: attestation.signature,
+    };
+    identity::verify_attestation(&attestation, &attester_public_key).map_err(|e| e.to_string())
+}
+
+/// Check `command` against the license-tier ACL before dispatching it to
+/// the unified backend. `requested` is an optional size/count (e.g. the
+/// share count `create_share` would bring the caller to) checked against
+/// the command's numeric limit, if it has one.
+pub async fn execute_unified_command_licensed(
+    command: &str,
+    args: serde_json::Value,
+    state: &AppState,
+    requested: Option<u64>,
+) -> Result<unified_backend::UnifiedResponse, String> {
+    let status = cached_license_status(state);
+    license_acl::check_command(command, &status, requested).map_err(|denied| denied.to_string())?;
+    operation_log::execute_offline_first(command, args).await
+}
+
+/// The current number of shares the caller has created, used to enforce
+/// `create_share`'s `max_shares` limit.
+async fn current_share_count() -> Option<u64> {
+    let result = execute_unified_command("get_shares", serde_json::json!({})).await.ok()?;
+    if !result.success {
+        return None;
+    }
+    result.data?.as_array().map(|shares| shares.len() as u64)
+}
+
+#[tauri::command]
+async fn get_command_permissions() -> Result<Vec<license_acl::CommandPermission>, String> {
+    Ok(license_acl::command_permissions())
 }
 
 #[tauri::command]
@@ -347,68 +915,224 @@ async fn select_files(app: tauri::AppHandle) -> Result<Vec<FileNode>, String> {
 }
 
 #[tauri::command]
-async fn select_folder(app: tauri::AppHandle) -> Result<FileNode, String> {
+async fn select_folder(app: tauri::AppHandle, compute_hashes: bool) -> Result<IndexResult, String> {
     use tauri_plugin_dialog::DialogExt;
-    
+
     let folder = app.dialog()
         .file()
         .set_title("Select Folder")
         .blocking_pick_folder()
         .ok_or_else(|| "No folder selected".to_string())?;
-    
+
     let path = folder.as_path()
         .ok_or_else(|| "Invalid folder path".to_string())?;
-    
-    index_folder_recursive(&path.to_path_buf())
+
+    index_folder_tree(path.to_path_buf(), app, compute_hashes).await
 }
 
 #[tauri::command]
-async fn index_folder(path: String) -> Result<FileNode, String> {
+async fn index_folder(path: String, app: tauri::AppHandle, compute_hashes: bool) -> Result<IndexResult, String> {
     let path = PathBuf::from(path);
-    
+
     if !path.exists() {
         return Err("Path does not exist".to_string());
     }
-    
-    index_folder_recursive(&path)
+
+    index_folder_tree(path, app, compute_hashes).await
 }
 
-fn index_folder_recursive(path: &PathBuf) -> Result<FileNode, String> {
-    let metadata = std::fs::metadata(path)
-        .map_err(|e| e.to_string())?;
-    
-    let name = path.file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("unknown")
-        .to_string();
-    
-    if metadata.is_file() {
-        Ok(FileNode {
-            id: Uuid::new_v4().to_string(),
-            name,
-            node_type: "file".to_string(),
-            size: metadata.len(),
-            path: path.to_string_lossy().to_string(),
-            children: None,
-            selected: Some(false),
-            progress: None,
-            hash: None,
-            modified_at: chrono::Utc::now().to_rfc3339(),
-        })
-    } else {
-        let mut children = Vec::new();
-        let entries = std::fs::read_dir(path)
-            .map_err(|e| e.to_string())?;
-        
+/// Group nodes under `root` that share both `hash` and `size` (so, the
+/// same content), excluding nodes indexed without `compute_hashes`. Each
+/// returned cluster has at least two members -- a genuine duplicate
+/// candidate the UI can warn about before uploading redundant data.
+#[tauri::command]
+async fn find_duplicate_files(root: FileNode) -> Result<Vec<Vec<FileNode>>, String> {
+    let mut by_hash_and_size: HashMap<(String, u64), Vec<FileNode>> = HashMap::new();
+    collect_file_nodes(&root, &mut by_hash_and_size);
+
+    Ok(by_hash_and_size
+        .into_values()
+        .filter(|cluster| cluster.len() > 1)
+        .collect())
+}
+
+fn collect_file_nodes(node: &FileNode, by_hash_and_size: &mut HashMap<(String, u64), Vec<FileNode>>) {
+    if let Some(hash) = &node.hash {
+        by_hash_and_size
+            .entry((hash.clone(), node.size))
+            .or_default()
+            .push(node.clone());
+    }
+    if let Some(children) = &node.children {
+        for child in children {
+            collect_file_nodes(child, by_hash_and_size);
+        }
+    }
+}
+
+/// Default number of directories the recursive indexer reads concurrently.
+const DEFAULT_INDEX_CONCURRENCY: usize = 8;
+
+/// Buffer size used while streaming a file's contents through the hasher,
+/// so `compute_hashes` doesn't load a whole large file into memory.
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Stream `path`'s contents through SHA-256 in fixed-size chunks and return
+/// the hex digest, for `compute_hashes`-enabled indexing.
+fn hash_file_contents(path: &PathBuf) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Result of an async recursive index: the assembled tree, plus any entries
+/// skipped because `read_dir`/`metadata` failed (most commonly a permission
+/// error) rather than aborting the whole walk.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct IndexResult {
+    root: FileNode,
+    skipped: Vec<String>,
+}
+
+/// Incremental progress pushed to the frontend as the indexer discovers
+/// files, so a large tree fills in live instead of the command blocking
+/// silently for minutes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexProgress {
+    #[serde(rename = "filesFound")]
+    files_found: u64,
+    #[serde(rename = "bytesFound")]
+    bytes_found: u64,
+}
+
+/// State shared by every in-flight directory task of one `index_folder_tree`
+/// call: the semaphore bounding concurrent disk reads, the canonicalized
+/// visited-path set guarding against symlink cycles, running progress
+/// counters, and the skipped-entry log.
+struct IndexContext {
+    semaphore: Arc<Semaphore>,
+    visited: Mutex<HashSet<PathBuf>>,
+    files_found: AtomicU64,
+    bytes_found: AtomicU64,
+    skipped: Mutex<Vec<String>>,
+    app: tauri::AppHandle,
+    /// Whether to compute a streaming SHA-256 digest of each file's
+    /// contents, for `find_duplicate_files` to group on.
+    compute_hashes: bool,
+}
+
+impl IndexContext {
+    fn emit_progress(&self) {
+        let _ = self.app.emit(
+            "index-progress",
+            IndexProgress {
+                files_found: self.files_found.load(Ordering::Relaxed),
+                bytes_found: self.bytes_found.load(Ordering::Relaxed),
+            },
+        );
+    }
+}
+
+/// Async, concurrency-bounded recursive index of `path`. Each directory is
+/// read as its own task behind `ctx.semaphore`, with subdirectories
+/// scheduled back onto a `FuturesUnordered` stream so a wide tree fans out
+/// up to the permit count rather than serially.
+fn index_path_async(path: PathBuf, ctx: Arc<IndexContext>) -> BoxFuture<'static, Option<FileNode>> {
+    async move {
+        let _permit = ctx.semaphore.clone().acquire_owned().await.ok()?;
+
+        let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if !ctx.visited.lock().unwrap().insert(canonical) {
+            // Already visited via another path -- a symlink cycle. Skip
+            // silently rather than recursing forever.
+            return None;
+        }
+
+        let metadata = match std::fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                ctx.skipped.lock().unwrap().push(format!("{}: {}", path.display(), e));
+                return None;
+            }
+        };
+
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        if metadata.is_file() {
+            ctx.files_found.fetch_add(1, Ordering::Relaxed);
+            ctx.bytes_found.fetch_add(metadata.len(), Ordering::Relaxed);
+            ctx.emit_progress();
+
+            let hash = if ctx.compute_hashes {
+                let hash_path = path.clone();
+                tokio::task::spawn_blocking(move || hash_file_contents(&hash_path))
+                    .await
+                    .ok()
+                    .and_then(|r| r.ok())
+            } else {
+                None
+            };
+
+            return Some(FileNode {
+                id: Uuid::new_v4().to_string(),
+                name,
+                node_type: "file".to_string(),
+                size: metadata.len(),
+                path: path.to_string_lossy().to_string(),
+                children: None,
+                selected: Some(false),
+                progress: None,
+                hash,
+                modified_at: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+
+        let entries = match std::fs::read_dir(&path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                ctx.skipped.lock().unwrap().push(format!("{}: {}", path.display(), e));
+                return None;
+            }
+        };
+
+        let mut tasks = FuturesUnordered::new();
         for entry in entries {
-            let entry = entry.map_err(|e| e.to_string())?;
-            let child_path = entry.path();
-            if let Ok(child_node) = index_folder_recursive(&child_path) {
-                children.push(child_node);
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    ctx.skipped.lock().unwrap().push(format!("{}: {}", path.display(), e));
+                    continue;
+                }
+            };
+            tasks.push(index_path_async(entry.path(), ctx.clone()));
+        }
+        // This directory's read is done; release its permit before waiting
+        // on the children, which need permits of their own.
+        drop(_permit);
+
+        let mut children = Vec::new();
+        while let Some(child) = tasks.next().await {
+            if let Some(node) = child {
+                children.push(node);
             }
         }
-        
-        Ok(FileNode {
+
+        Some(FileNode {
             id: Uuid::new_v4().to_string(),
             name,
             node_type: "folder".to_string(),
@@ -421,6 +1145,30 @@ fn index_folder_recursive(path: &PathBuf) -> Result<FileNode, String> {
             modified_at: chrono::Utc::now().to_rfc3339(),
         })
     }
+    .boxed()
+}
+
+async fn index_folder_tree(
+    path: PathBuf,
+    app: tauri::AppHandle,
+    compute_hashes: bool,
+) -> Result<IndexResult, String> {
+    let ctx = Arc::new(IndexContext {
+        semaphore: Arc::new(Semaphore::new(DEFAULT_INDEX_CONCURRENCY)),
+        visited: Mutex::new(HashSet::new()),
+        files_found: AtomicU64::new(0),
+        bytes_found: AtomicU64::new(0),
+        skipped: Mutex::new(Vec::new()),
+        app,
+        compute_hashes,
+    });
+
+    let root = index_path_async(path, ctx.clone())
+        .await
+        .ok_or_else(|| "Failed to index path".to_string())?;
+
+    let skipped = ctx.skipped.lock().unwrap().clone();
+    Ok(IndexResult { root, skipped })
 }
 
 // Share Operations
@@ -429,6 +1177,8 @@ async fn create_share(
     files: Vec<String>,
     share_type: String,
     password: Option<String>,
+    app_state: State<'_, AppState>,
+    system_state: State<'_, SystemState>,
 ) -> Result<Share, String> {
     // Use unified backend with automatic fallback
     let args = serde_json::json!({
@@ -436,19 +1186,30 @@ async fn create_share(
         "share_type": share_type,
         "password": password
     });
-    
-    let result = execute_unified_command("create_share", args)
+
+    // Checked as the count this share would bring the caller to, so a
+    // caller already at `max_shares` is denied.
+    let shares_after = current_share_count().await.map(|count| count + 1);
+    let result = execute_unified_command_licensed("create_share", args, &app_state, shares_after)
+        .await
         .map_err(|e| format!("Failed to create share: {}", e))?;
-    
+
     // Parse response into Share struct
     if result.success {
         if let Some(data) = result.data {
-            let share: Share = serde_json::from_value(data)
+            let mut share: Share = serde_json::from_value(data)
                 .map_err(|e| format!("Failed to parse share: {}", e))?;
+            // Hand back a short, human-typeable share code instead of the
+            // backend's raw hex id -- `normalize_share_id` already decodes
+            // one of these back to the raw id on the download side.
+            if let Ok(raw_id) = hex::decode(&share.share_id) {
+                share.share_id = share_code::encode_share_code(&raw_id);
+            }
+            system_state.record_share_created();
             return Ok(share);
         }
     }
-    
+
     Err(result.error.unwrap_or_else(|| "Unknown error".to_string()))
 }
 
@@ -456,6 +1217,7 @@ async fn create_share(
 async fn get_shares() -> Result<Vec<Share>, String> {
     // Use unified backend
     let result = execute_unified_command("get_shares", serde_json::json!({}))
+        .await
         .map_err(|e| format!("Failed to get shares: {}", e))?;
     
     if result.success {
@@ -474,21 +1236,87 @@ async fn download_share(
     share_id: String,
     destination: String,
     selected_files: Option<Vec<String>>,
+    app_state: State<'_, AppState>,
+    system_state: State<'_, SystemState>,
+    app: tauri::AppHandle,
 ) -> Result<(), String> {
+    let transfer_id = uuid::Uuid::new_v4().to_string();
+    transfers::start(transfer_id.clone(), share_id.clone(), "download", 0);
+    app_state.transfers.lock().unwrap().insert(
+        transfer_id.clone(),
+        Transfer {
+            id: transfer_id.clone(),
+            transfer_type: "download".to_string(),
+            name: share_id.clone(),
+            total_size: 0,
+            transferred_size: 0,
+            speed: 0.0,
+            eta: 0,
+            status: "active".to_string(),
+            segments: Vec::new(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            completed_at: None,
+            error: None,
+        },
+    );
+
+    // Accept a share code as handed out by `create_share`, or a raw id.
+    let share_id = share_code::decode_share_code(&share_id).map(hex::encode).unwrap_or(share_id);
+
     // Use unified backend
     let args = serde_json::json!({
         "share_id": share_id,
         "destination": destination,
-        "selected_files": selected_files
+        "selected_files": selected_files,
+        "transfer_id": transfer_id
     });
-    
-    let result = execute_unified_command("download_share", args)
-        .map_err(|e| format!("Failed to download share: {}", e))?;
-    
+
+    let result = execute_unified_command_licensed("download_share", args, &app_state, None).await;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            finish_transfer(&app_state, &app, &transfer_id, "failed", Some(e.clone())).await;
+            return Err(format!("Failed to download share: {}", e));
+        }
+    };
+
     if result.success {
+        system_state.record_download_started();
+        let bytes = result.data.as_ref().and_then(|d| d.get("bytes_transferred")).and_then(|v| v.as_u64());
+        if let Some(bytes) = bytes {
+            system_state.record_bytes_downloaded(bytes);
+            transfers::update_progress(&transfer_id, bytes, 0);
+        }
+        finish_transfer(&app_state, &app, &transfer_id, "completed", None).await;
         Ok(())
     } else {
-        Err(result.error.unwrap_or_else(|| "Download failed".to_string()))
+        let error = result.error.unwrap_or_else(|| "Download failed".to_string());
+        finish_transfer(&app_state, &app, &transfer_id, "failed", Some(error.clone())).await;
+        Err(error)
+    }
+}
+
+/// Mark a transfer's terminal state in-memory and on disk, and emit
+/// `"transfer-status-changed"` so the UI stops showing it as in-flight.
+async fn finish_transfer(
+    app_state: &AppState,
+    app: &tauri::AppHandle,
+    transfer_id: &str,
+    status: &str,
+    error: Option<String>,
+) {
+    if let Some(transfer) = app_state.transfers.lock().unwrap().get_mut(transfer_id) {
+        transfer.status = status.to_string();
+        transfer.completed_at = Some(chrono::Utc::now().to_rfc3339());
+        transfer.error = error;
+    }
+    let record = transfers::set_status(transfer_id, status);
+    if status == "completed" || status == "failed" {
+        transfers::remove(transfer_id);
+    }
+    if let Some(record) = record {
+        let _ = app.emit("transfer-status-changed", &record);
     }
 }
 
@@ -500,6 +1328,7 @@ async fn get_share_details(share_id: String) -> Result<Share, String> {
     });
     
     let result = execute_unified_command("get_share_details", args)
+        .await
         .map_err(|e| format!("Failed to get share details: {}", e))?;
     
     if result.success {
@@ -515,6 +1344,83 @@ async fn get_share_details(share_id: String) -> Result<Share, String> {
     }
 }
 
+// Feed Watcher Commands
+#[tauri::command]
+async fn add_feed(url: String, filter: feeds::FeedFilter) -> Result<feeds::Feed, String> {
+    Ok(feeds::add_feed(url, filter))
+}
+
+#[tauri::command]
+async fn list_feeds() -> Result<Vec<feeds::Feed>, String> {
+    Ok(feeds::list_feeds())
+}
+
+#[tauri::command]
+async fn remove_feed(feed_id: String) -> Result<(), String> {
+    feeds::remove_feed(&feed_id);
+    Ok(())
+}
+
+async fn fetch_share_file_tree(share_id: &str) -> Result<FileNode, String> {
+    let args = serde_json::json!({ "share_id": share_id });
+    let result = execute_unified_command("get_share_file_tree", args)
+        .await
+        .map_err(|e| format!("Failed to get share file tree: {}", e))?;
+
+    if result.success {
+        if let Some(data) = result.data {
+            return serde_json::from_value(data).map_err(|e| format!("Failed to parse share file tree: {}", e));
+        }
+    }
+
+    Err(result.error.unwrap_or_else(|| "Failed to get share file tree".to_string()))
+}
+
+/// Start a read-only WebDAV gateway exposing `share_id`'s file tree on
+/// `bind_addr`, capped to the license tier's `max_connections`. Replaces
+/// any gateway already running for this app instance. `password` must be
+/// the same password the share was created with (`None` for a share that
+/// has none); the gateway requires it as HTTP Basic Auth on every request
+/// rather than serving a password-protected share unauthenticated, since
+/// `bind_addr` is caller-supplied and not limited to loopback.
+#[tauri::command]
+async fn start_webdav_gateway(
+    share_id: String,
+    bind_addr: String,
+    password: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    if let Some(existing) = state.webdav_gateway.lock().unwrap().take() {
+        existing.shutdown().await;
+    }
+
+    let root = fetch_share_file_tree(&share_id).await?;
+
+    let max_connections = state
+        .license
+        .lock()
+        .unwrap()
+        .get_feature_value("max_connections")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(10);
+
+    let handle = webdav::start(share_id, bind_addr, root, max_connections, password).await?;
+    let bound_addr = handle.bind_addr.clone();
+    *state.webdav_gateway.lock().unwrap() = Some(handle);
+
+    Ok(bound_addr)
+}
+
+/// Stop the running WebDAV gateway, if any.
+#[tauri::command]
+async fn stop_webdav_gateway(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(handle) = state.webdav_gateway.lock().unwrap().take() {
+        handle.shutdown().await;
+    }
+    Ok(())
+}
+
 // Folder Management Commands
 #[tauri::command]
 async fn add_folder(path: String, name: Option<String>) -> Result<serde_json::Value, String> {
@@ -523,7 +1429,8 @@ async fn add_folder(path: String, name: Option<String>) -> Result<serde_json::Va
         "name": name
     });
     
-    let result = execute_unified_command("add_folder", args)
+    let result = operation_log::execute_offline_first("add_folder", args)
+        .await
         .map_err(|e| format!("Failed to add folder: {}", e))?;
     
     if result.success {
@@ -539,7 +1446,8 @@ async fn index_folder_full(folder_id: String) -> Result<serde_json::Value, Strin
         "folder_id": folder_id
     });
     
-    let result = execute_unified_command("index_folder", args)
+    let result = operation_log::execute_offline_first("index_folder", args)
+        .await
         .map_err(|e| format!("Failed to index folder: {}", e))?;
     
     if result.success {
@@ -555,7 +1463,8 @@ async fn segment_folder(folder_id: String) -> Result<serde_json::Value, String>
         "folder_id": folder_id
     });
     
-    let result = execute_unified_command("segment_folder", args)
+    let result = operation_log::execute_offline_first("segment_folder", args)
+        .await
         .map_err(|e| format!("Failed to segment folder: {}", e))?;
     
     if result.success {
@@ -566,27 +1475,70 @@ async fn segment_folder(folder_id: String) -> Result<serde_json::Value, String>
 }
 
 #[tauri::command]
-async fn upload_folder(folder_id: String) -> Result<serde_json::Value, String> {
+async fn upload_folder(
+    folder_id: String,
+    app_state: State<'_, AppState>,
+    system_state: State<'_, SystemState>,
+    app: tauri::AppHandle,
+) -> Result<serde_json::Value, String> {
+    let transfer_id = uuid::Uuid::new_v4().to_string();
+    transfers::start(transfer_id.clone(), folder_id.clone(), "upload", 0);
+    app_state.transfers.lock().unwrap().insert(
+        transfer_id.clone(),
+        Transfer {
+            id: transfer_id.clone(),
+            transfer_type: "upload".to_string(),
+            name: folder_id.clone(),
+            total_size: 0,
+            transferred_size: 0,
+            speed: 0.0,
+            eta: 0,
+            status: "active".to_string(),
+            segments: Vec::new(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            completed_at: None,
+            error: None,
+        },
+    );
+
     let args = serde_json::json!({
-        "folder_id": folder_id
+        "folder_id": folder_id,
+        "transfer_id": transfer_id
     });
-    
-    let result = execute_unified_command("upload_folder", args)
-        .map_err(|e| format!("Failed to upload folder: {}", e))?;
-    
+
+    let result = execute_unified_command_licensed("upload_folder", args, &app_state, None).await;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            finish_transfer(&app_state, &app, &transfer_id, "failed", Some(e.clone())).await;
+            return Err(format!("Failed to upload folder: {}", e));
+        }
+    };
+
     if result.success {
+        system_state.record_upload_queued();
+        let bytes = result.data.as_ref().and_then(|d| d.get("bytes_transferred")).and_then(|v| v.as_u64());
+        if let Some(bytes) = bytes {
+            system_state.record_bytes_uploaded(bytes);
+            transfers::update_progress(&transfer_id, bytes, 0);
+        }
+        finish_transfer(&app_state, &app, &transfer_id, "completed", None).await;
         Ok(result.data.unwrap_or(serde_json::json!({})))
     } else {
-        Err(result.error.unwrap_or_else(|| "Failed to upload folder".to_string()))
+        let error = result.error.unwrap_or_else(|| "Failed to upload folder".to_string());
+        finish_transfer(&app_state, &app, &transfer_id, "failed", Some(error.clone())).await;
+        Err(error)
     }
 }
 
 #[tauri::command]
 async fn publish_folder(
-    folder_id: String, 
+    folder_id: String,
     access_type: Option<String>,
     user_ids: Option<Vec<String>>,
-    password: Option<String>
+    password: Option<String>,
+    app_state: State<'_, AppState>,
 ) -> Result<serde_json::Value, String> {
     let args = serde_json::json!({
         "folder_id": folder_id,
@@ -594,8 +1546,9 @@ async fn publish_folder(
         "user_ids": user_ids,
         "password": password
     });
-    
-    let result = execute_unified_command("publish_folder", args)
+
+    let result = execute_unified_command_licensed("publish_folder", args, &app_state, None)
+        .await
         .map_err(|e| format!("Failed to publish folder: {}", e))?;
     
     if result.success {
@@ -612,7 +1565,8 @@ async fn add_authorized_user(folder_id: String, user_id: String) -> Result<serde
         "user_id": user_id
     });
     
-    let result = execute_unified_command("add_authorized_user", args)
+    let result = operation_log::execute_offline_first("add_authorized_user", args)
+        .await
         .map_err(|e| format!("Failed to add authorized user: {}", e))?;
     
     if result.success {
@@ -629,7 +1583,8 @@ async fn remove_authorized_user(folder_id: String, user_id: String) -> Result<se
         "user_id": user_id
     });
     
-    let result = execute_unified_command("remove_authorized_user", args)
+    let result = operation_log::execute_offline_first("remove_authorized_user", args)
+        .await
         .map_err(|e| format!("Failed to remove authorized user: {}", e))?;
     
     if result.success {
@@ -646,6 +1601,7 @@ async fn get_authorized_users(folder_id: String) -> Result<serde_json::Value, St
     });
     
     let result = execute_unified_command("get_authorized_users", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
     
     if result.success {
@@ -672,6 +1628,7 @@ async fn get_folders() -> Result<Vec<serde_json::Value>, String> {
     let args = serde_json::json!({});
     
     let result = execute_unified_command("get_folders", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
     
     if result.success {
@@ -704,6 +1661,7 @@ async fn get_user_info() -> Result<serde_json::Value, String> {
     let args = serde_json::json!({});
     
     let result = execute_unified_command("get_user_info", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
     
     if result.success {
@@ -728,7 +1686,8 @@ async fn initialize_user(display_name: Option<String>) -> Result<String, String>
         "display_name": display_name
     });
     
-    let result = execute_unified_command("initialize_user", args)
+    let result = operation_log::execute_offline_first("initialize_user", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
     
     if result.success {
@@ -767,6 +1726,7 @@ async fn is_user_initialized() -> Result<bool, String> {
     let args = serde_json::json!({});
     
     let result = execute_unified_command("is_user_initialized", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
     
     if result.success {
@@ -802,7 +1762,8 @@ async fn set_folder_access(folder_id: String, access_type: String, password: Opt
         "password": password
     });
     
-    let result = execute_unified_command("set_folder_access", args)
+    let result = operation_log::execute_offline_first("set_folder_access", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
     
     if result.success {
@@ -830,14 +1791,19 @@ async fn set_folder_access(folder_id: String, access_type: String, password: Opt
 }
 
 #[tauri::command]
-async fn folder_info(folder_id: String) -> Result<serde_json::Value, String> {
+async fn folder_info(folder_id: String, app_state: State<'_, AppState>) -> Result<serde_json::Value, String> {
     let args = serde_json::json!({
         "folder_id": folder_id
     });
-    
-    let result = execute_unified_command("folder_info", args)
+
+    // Read-only and polled frequently by the UI, so it's served through
+    // the pool's short-TTL cache.
+    let result = app_state
+        .backend_pool
+        .call_cached("folder_info", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
-    
+
     if result.success {
         Ok(result.data.unwrap_or(serde_json::json!({})))
     } else {
@@ -858,14 +1824,19 @@ async fn folder_info(folder_id: String) -> Result<serde_json::Value, String> {
 }
 
 #[tauri::command]
-async fn resync_folder(folder_id: String) -> Result<serde_json::Value, String> {
+async fn resync_folder(folder_id: String, app_state: State<'_, AppState>) -> Result<serde_json::Value, String> {
     let args = serde_json::json!({
         "folder_id": folder_id
     });
-    
-    let result = execute_unified_command("resync_folder", args)
+
+    // Mutates folder state, so it goes through the pool uncached -- only
+    // the in-flight concurrency bound applies here.
+    let result = app_state
+        .backend_pool
+        .call("resync_folder", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
-    
+
     if result.success {
         Ok(result.data.unwrap_or(serde_json::json!({})))
     } else {
@@ -892,7 +1863,8 @@ async fn delete_folder(folder_id: String, confirm: bool) -> Result<serde_json::V
         "confirm": confirm
     });
     
-    let result = execute_unified_command("delete_folder", args)
+    let result = operation_log::execute_offline_first("delete_folder", args)
+        .await
         .map_err(|e| format!("Failed to execute command: {}", e))?;
     
     if result.success {
@@ -904,91 +1876,121 @@ async fn delete_folder(folder_id: String, confirm: bool) -> Result<serde_json::V
 
 // Transfer Operations
 #[tauri::command]
-async fn pause_transfer(state: State<'_, AppState>, transfer_id: String) -> Result<(), String> {
-    let mut transfers = state.transfers.lock().unwrap();
-    
-    if let Some(transfer) = transfers.get_mut(&transfer_id) {
-        transfer.status = "paused".to_string();
-        Ok(())
-    } else {
-        Err("Transfer not found".to_string())
+async fn get_transfers(state: State<'_, AppState>) -> Result<Vec<Transfer>, String> {
+    Ok(state.transfers.lock().unwrap().values().cloned().collect())
+}
+
+/// Set `transfer_id`'s status in-memory and on disk, relay `backend_command`
+/// to the engine so it can honor the change between segments, flip the
+/// shared control flag, and emit a `"transfer-status-changed"` event.
+async fn set_transfer_status(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    transfer_id: &str,
+    status: &str,
+    backend_command: &str,
+) -> Result<(), String> {
+    {
+        let mut transfers = state.transfers.lock().unwrap();
+        let transfer = transfers.get_mut(transfer_id).ok_or_else(|| "Transfer not found".to_string())?;
+        transfer.status = status.to_string();
     }
+
+    transfers::set_control(transfer_id, status);
+    let record = transfers::set_status(transfer_id, status);
+
+    let _ = execute_unified_command(backend_command, serde_json::json!({ "transfer_id": transfer_id })).await;
+
+    if let Some(record) = record {
+        let _ = app.emit("transfer-status-changed", &record);
+    }
+
+    Ok(())
 }
 
 #[tauri::command]
-async fn resume_transfer(state: State<'_, AppState>, transfer_id: String) -> Result<(), String> {
-    let mut transfers = state.transfers.lock().unwrap();
-    
-    if let Some(transfer) = transfers.get_mut(&transfer_id) {
-        transfer.status = "active".to_string();
-        Ok(())
-    } else {
-        Err("Transfer not found".to_string())
-    }
+async fn pause_transfer(state: State<'_, AppState>, app: tauri::AppHandle, transfer_id: String) -> Result<(), String> {
+    set_transfer_status(&state, &app, &transfer_id, "paused", "pause_transfer").await
 }
 
 #[tauri::command]
-async fn cancel_transfer(state: State<'_, AppState>, transfer_id: String) -> Result<(), String> {
-    let mut transfers = state.transfers.lock().unwrap();
-    
-    if transfers.remove(&transfer_id).is_some() {
-        Ok(())
-    } else {
-        Err("Transfer not found".to_string())
-    }
+async fn resume_transfer(state: State<'_, AppState>, app: tauri::AppHandle, transfer_id: String) -> Result<(), String> {
+    set_transfer_status(&state, &app, &transfer_id, "active", "resume_transfer").await
 }
 
-// Database Commands
 #[tauri::command]
-async fn check_database_status() -> Result<serde_json::Value, String> {
-    let args = serde_json::json!({});
-    
-    let result = execute_unified_command("check_database_status", args)
-        .map_err(|e| format!("Failed to execute command: {}", e))?;
-    
-    if result.success {
-        Ok(result.data.unwrap_or(serde_json::json!({})))
-    } else {
-        Err(result.error.unwrap_or_else(|| "Command failed".to_string()))
-    }
+async fn cancel_transfer(state: State<'_, AppState>, app: tauri::AppHandle, transfer_id: String) -> Result<(), String> {
+    set_transfer_status(&state, &app, &transfer_id, "cancelled", "cancel_transfer").await?;
+    state.transfers.lock().unwrap().remove(&transfer_id);
+    transfers::remove(&transfer_id);
+    Ok(())
 }
-    
-    let output = cmd.arg("check-database").output().map_err(|e| e.to_string())?;
-    
-    if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
-    }
-    
-    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+
+// Database Commands
+#[tauri::command]
+async fn check_database_status(app_state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+    database::check_status(&app_state).await
 }
 
+/// Kept for existing callers; now a thin wrapper over [`setup_database`]
+/// pinned to the `postgresql` backend.
 #[tauri::command]
 async fn setup_postgresql() -> Result<serde_json::Value, String> {
-    let args = serde_json::json!({});
-    
-    let result = execute_unified_command("setup_postgresql", args)
-        .map_err(|e| format!("Failed to execute command: {}", e))?;
-    
-    if result.success {
-        Ok(result.data.unwrap_or(serde_json::json!({})))
-    } else {
-        Err(result.error.unwrap_or_else(|| "Command failed".to_string()))
-    }
+    database::setup(database::DatabaseBackend::Postgresql, None).await
+}
+
+/// Provision whichever backend `backend` names (SQLite needs no `config`;
+/// PostgreSQL expects connection details there).
+#[tauri::command]
+async fn setup_database(
+    backend: database::DatabaseBackend,
+    config: Option<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    database::setup(backend, config).await
+}
+
+/// Move existing folder/share metadata from one backend to another, e.g.
+/// an existing Postgres install switching to the embedded SQLite default.
+#[tauri::command]
+async fn migrate_database(
+    from: database::DatabaseBackend,
+    to: database::DatabaseBackend,
+) -> Result<serde_json::Value, String> {
+    database::migrate(from, to).await
 }
 
 // Server Configuration
 #[tauri::command]
-async fn test_server_connection(config: ServerConfig) -> Result<bool, String> {
-    // Use unified backend to test connection
-    let args = serde_json::json!({
-        "hostname": config.hostname,
-        "port": config.port,
-        "username": config.username,
-        "password": config.password,
-        "use_ssl": config.use_ssl
-    });
-    
-    let result = execute_unified_command("test_server_connection", args)
+async fn test_server_connection(config: ServerConfig, app_state: State<'_, AppState>) -> Result<bool, String> {
+    // Hand the backend a completed SASL client-first exchange instead of
+    // the raw password where possible -- see `sasl` for why. There's no
+    // live connection yet to learn which mechanisms the server actually
+    // advertises (establishing one is this command's whole job), so this
+    // negotiates against PLAIN, the one mechanism with a self-contained
+    // client-first message; falling back to the raw password only if
+    // that negotiation somehow fails.
+    let args = match sasl::client_first_message(&["PLAIN".to_string()], &config.username, &config.password) {
+        Some((mechanism, response)) => serde_json::json!({
+            "hostname": config.hostname,
+            "port": config.port,
+            "username": config.username,
+            "sasl_mechanism": mechanism.name(),
+            "sasl_response": response,
+            "use_ssl": config.use_ssl
+        }),
+        None => serde_json::json!({
+            "hostname": config.hostname,
+            "port": config.port,
+            "username": config.username,
+            "password": config.password,
+            "use_ssl": config.use_ssl
+        }),
+    };
+
+    let result = app_state
+        .backend_pool
+        .call("test_server_connection", args)
+        .await
         .map_err(|e| format!("Failed to test connection: {}", e))?;
     
     if result.success {
@@ -1001,78 +2003,68 @@ async fn test_server_connection(config: ServerConfig) -> Result<bool, String> {
 }
 
 #[tauri::command]
-async fn save_server_config(config: ServerConfig) -> Result<(), String> {
-    // Save to config file
-    let config_path = dirs::config_dir()
-        .ok_or_else(|| "Could not find config directory".to_string())?
-        .join("usenet-sync")
-        .join("server.json");
-    
-    std::fs::create_dir_all(config_path.parent().unwrap())
-        .map_err(|e| e.to_string())?;
-    
-    let json = serde_json::to_string_pretty(&config)
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(config_path, json)
-        .map_err(|e| e.to_string())?;
-    
+async fn save_server_config(config: ServerConfig, app_state: State<'_, AppState>) -> Result<(), String> {
+    let key = app_state
+        .master_key
+        .lock()
+        .unwrap()
+        .ok_or_else(|| "Vault is locked; call set_master_passphrase or unlock first".to_string())?;
+
+    credentials::save(&config, &key)
+}
+
+/// Decrypt and return the previously saved server config. Requires the
+/// vault to be unlocked; the password is decrypted lazily here rather than
+/// kept plaintext anywhere, so callers like `test_server_connection` only
+/// ever see it right before they need it.
+#[tauri::command]
+async fn get_server_config(app_state: State<'_, AppState>) -> Result<ServerConfig, String> {
+    let key = app_state
+        .master_key
+        .lock()
+        .unwrap()
+        .ok_or_else(|| "Vault is locked; call set_master_passphrase or unlock first".to_string())?;
+
+    credentials::load(&key)
+}
+
+/// First-time setup: derive a key from `passphrase` and hold it in memory
+/// for this session. If a server config is already stored, the passphrase
+/// must decrypt it, the same check `unlock` makes.
+#[tauri::command]
+async fn set_master_passphrase(passphrase: String, app_state: State<'_, AppState>) -> Result<(), String> {
+    let key = credentials::derive_and_verify(&passphrase)?;
+    *app_state.master_key.lock().unwrap() = Some(key);
+    Ok(())
+}
+
+/// Re-derive the key from `passphrase` on a later launch. If a server
+/// config is already stored, the passphrase must actually decrypt it --
+/// fails closed on a wrong passphrase instead of unlocking with a key that
+/// can't read anything.
+#[tauri::command]
+async fn unlock(passphrase: String, app_state: State<'_, AppState>) -> Result<(), String> {
+    let key = credentials::derive_and_verify(&passphrase)?;
+    *app_state.master_key.lock().unwrap() = Some(key);
     Ok(())
 }
 
 // System Operations
 #[tauri::command]
 async fn get_system_stats() -> Result<SystemStats, String> {
-    use sysinfo::{System, Disks};
-    
-    let mut sys = System::new_all();
-    sys.refresh_all();
-    
-    // Get real CPU usage
-    let cpu_usage = sys.global_cpu_info().cpu_usage();
-    
-    // Get real memory usage
-    let memory_usage = if sys.total_memory() > 0 {
-        (sys.used_memory() as f32 / sys.total_memory() as f32) * 100.0
-    } else {
-        0.0
-    };
-    
-    // Get disk usage
-    let disks = Disks::new_with_refreshed_list();
-    let disk_usage = disks.list()
-        .iter()
-        .map(|disk| {
-            if disk.total_space() > 0 {
-                let used = disk.total_space() - disk.available_space();
-                (used as f32 / disk.total_space() as f32) * 100.0
-            } else {
-                0.0
-            }
-        })
-        .next()
-        .unwrap_or(0.0);
-    
-    // Network speed and transfer counts would need to be tracked over time
-    // For now, return 0 instead of fake data
-    let network_speed = NetworkSpeed {
-        upload: 0.0,
-        download: 0.0,
-    };
-    
-    // Active transfers and shares should come from actual application state
-    // For now, return 0 instead of fake data
-    let active_transfers = 0;
-    let total_shares = 0;
-    
-    Ok(SystemStats {
-        cpu_usage,
-        memory_usage,
-        disk_usage,
-        network_speed,
-        active_transfers,
-        total_shares,
-    })
+    // The real work happens in `stats_sampler::watch`, ticking once a
+    // second since startup; this is just a snapshot read of its last
+    // sample rather than a fresh one-shot poll.
+    Ok(stats_sampler::latest())
+}
+
+/// Opt in or out of the Discord Rich Presence integration. Connecting and
+/// disconnecting happens on `discord_presence::watch`'s own tick rather
+/// than here, so this just flips the flag it reads.
+#[tauri::command]
+async fn toggle_discord_presence(enable: bool, app_state: State<'_, AppState>) -> Result<(), String> {
+    *app_state.discord_presence_enabled.lock().unwrap() = enable;
+    Ok(())
 }
 
 #[tauri::command]
@@ -1104,6 +2096,17 @@ async fn open_folder(path: String) -> Result<(), String> {
     Ok(())
 }
 
+// Offline-first operation queue
+#[tauri::command]
+async fn get_pending_operations() -> Result<Vec<operation_log::QueuedOperation>, String> {
+    Ok(operation_log::pending_operations())
+}
+
+#[tauri::command]
+async fn sync_pending_operations() -> Result<(), String> {
+    operation_log::sync().await
+}
+
 fn main() {
     // Initialize TurboActivate
     let license = TurboActivate::new(None).unwrap_or_else(|_| {
@@ -1111,31 +2114,134 @@ fn main() {
             .expect("Failed to initialize TurboActivate")
     });
     
+    let max_connections = license
+        .get_feature_value("max_connections")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(10);
+
+    // The Ed25519 license path keeps its own identity manager internally
+    // (licenses are signed against it); `AppState::identity_manager` is a
+    // separate instance for the device-list/SAS commands, which operate on
+    // this device's identity independently of whether a paid license is
+    // active.
+    let mut license_identity = identity::IdentityManager::new();
+    let _ = license_identity.initialize_identity();
+    let mut license_manager = license::LicenseManager::new(license_identity);
+    // Server-backed activation/revocation enforcement is opt-in: without
+    // this set, LicenseManager stays fully offline and only enforces
+    // max_activations against the local keyring counter.
+    if let Ok(server_url) = std::env::var("USENETSYNC_LICENSE_SERVER_URL") {
+        license_manager = license_manager
+            .with_server_client(Box::new(license::HttpLicenseServerClient::new(server_url)));
+    }
+
+    let mut identity_manager = identity::IdentityManager::new();
+    let _ = identity_manager.initialize_identity();
+
     let app_state = AppState {
         license: Mutex::new(license),
         python_process: Mutex::new(None),
         transfers: Mutex::new(HashMap::new()),
+        webdav_gateway: Mutex::new(None),
+        license_status_cache: Mutex::new(None),
+        backend_pool: backend_pool::BackendPool::new(max_connections),
+        master_key: Mutex::new(None),
+        discord_presence_enabled: Mutex::new(false),
+        license_manager: Mutex::new(license_manager),
+        license_chain: Mutex::new(load_license_chain()),
+        offline_license: Mutex::new(None),
+        hardware_fingerprint_reference: Mutex::new(None),
+        identity_manager: Mutex::new(identity_manager),
+        device_lists: Mutex::new(load_device_lists()),
+        sas_sessions: Mutex::new(HashMap::new()),
     };
-    
+
+    // Reload any transfer that was still active or paused when the app last
+    // exited, so the UI can offer to resume it instead of it silently
+    // vanishing with the in-memory map that used to be the only record.
+    {
+        let mut transfers = app_state.transfers.lock().unwrap();
+        for record in transfers::resumable() {
+            transfers::set_control(&record.id, "paused");
+            transfers.insert(
+                record.id.clone(),
+                Transfer {
+                    id: record.id,
+                    transfer_type: record.direction,
+                    name: record.folder_id,
+                    total_size: record.total_size,
+                    transferred_size: record.byte_offset,
+                    speed: 0.0,
+                    eta: 0,
+                    status: "paused".to_string(),
+                    segments: Vec::new(),
+                    started_at: chrono::Utc::now().to_rfc3339(),
+                    completed_at: None,
+                    error: None,
+                },
+            );
+        }
+    }
+
+    // `--headless --listen ADDR`: serve the unified commands over a local
+    // socket instead of opening the Tauri GUI window, for cron jobs and
+    // remote control.
+    if let Some(config) = daemon::parse_args() {
+        tauri::async_runtime::block_on(daemon::run(config, Arc::new(app_state)));
+        return;
+    }
+
     let system_state = init_system_commands();
-    
+
+    tauri::async_runtime::spawn(metrics::serve(
+        system_state.metrics_handle(),
+        metrics::metrics_port(),
+    ));
+
+    let stats_metrics_handle = system_state.metrics_handle();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .manage(app_state)
         .manage(system_state)
+        .setup(move |app| {
+            tauri::async_runtime::spawn(feeds::watch(app.handle().clone()));
+            tauri::async_runtime::spawn(stats_sampler::watch(app.handle().clone(), stats_metrics_handle));
+            discord_presence::watch(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             activate_license,
             check_license,
             start_trial,
             deactivate_license,
+            activate_vendor_license_key,
+            activate_license_chain,
+            activate_offline_license,
+            create_device_list,
+            add_trusted_device,
+            verify_device_list_signature,
+            start_sas_verification,
+            sas_receive_peer_commitment,
+            sas_reveal_peer_ephemeral_key,
+            confirm_sas_verification,
+            verify_sas_attestation,
+            get_command_permissions,
             select_files,
             select_folder,
             index_folder,
+            find_duplicate_files,
             create_share,
             get_shares,
             download_share,
             get_share_details,
+            start_webdav_gateway,
+            stop_webdav_gateway,
+            add_feed,
+            list_feeds,
+            remove_feed,
             add_folder,
             index_folder_full,
             segment_folder,
@@ -1154,17 +2260,28 @@ fn main() {
             get_user_info,
             initialize_user,
             is_user_initialized,
+            get_transfers,
             pause_transfer,
             resume_transfer,
             cancel_transfer,
             check_database_status,
             setup_postgresql,
+            setup_database,
+            migrate_database,
             test_server_connection,
             save_server_config,
+            get_server_config,
+            set_master_passphrase,
+            unlock,
             get_system_stats,
+            toggle_discord_presence,
             open_folder,
+            get_pending_operations,
+            sync_pending_operations,
             // System commands
             commands::get_logs,
+            commands::start_log_stream,
+            commands::stop_log_stream,
             commands::set_bandwidth_limit,
             commands::get_bandwidth_limit,
             commands::get_statistics,
@@ -1173,6 +2290,8 @@ fn main() {
             commands::clear_cache,
             commands::get_system_info,
             commands::restart_services,
+            commands::get_protocol_version,
+            commands::get_backend_version,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
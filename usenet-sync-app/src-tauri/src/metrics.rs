@@ -0,0 +1,172 @@
+// Embedded Prometheus metrics endpoint.
+//
+// Binds a plain HTTP/1.1 listener on localhost and answers `GET /metrics`
+// with the text exposition format, so the app can be scraped by standard
+// monitoring tooling without a Tauri round-trip for every sample. Mirrors
+// the minimal, unauthenticated admin endpoint garage's server exposes
+// alongside its main API — one scrape target, nothing fancier.
+
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::commands::system::{MetricsHandle, NetworkSpeed};
+
+/// Long-lived across scrapes so CPU usage has a delta to measure from --
+/// sysinfo needs two refreshes spread over time to compute it; a System
+/// created fresh on every scrape always reads ~0%.
+static SYSTEM: Lazy<Mutex<sysinfo::System>> = Lazy::new(|| Mutex::new(sysinfo::System::new_all()));
+
+/// Default port the metrics server listens on; overridden by the
+/// `USENETSYNC_METRICS_PORT` environment variable.
+const DEFAULT_METRICS_PORT: u16 = 9897;
+
+/// Placeholder disk figures, matching `get_statistics` until real disk
+/// stats are wired up (see the comment there).
+const PLACEHOLDER_TOTAL_DISK: u64 = 1_000_000_000;
+const PLACEHOLDER_USED_DISK: u64 = 500_000_000;
+
+pub fn metrics_port() -> u16 {
+    std::env::var("USENETSYNC_METRICS_PORT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_METRICS_PORT)
+}
+
+/// Bind the metrics listener and serve scrapes forever. Intended to be
+/// spawned once on `tauri::async_runtime` at startup; a bind failure (e.g.
+/// the port is already taken by another instance) is logged and the task
+/// simply exits rather than taking down the app.
+pub async fn serve(handle: MetricsHandle, port: u16) {
+    let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("metrics: failed to bind 127.0.0.1:{}: {}", port, e);
+            return;
+        }
+    };
+
+    loop {
+        let (socket, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(_) => continue,
+        };
+        let handle = handle.clone();
+        tokio::spawn(handle_connection(socket, handle));
+    }
+}
+
+async fn handle_connection(mut socket: tokio::net::TcpStream, handle: MetricsHandle) {
+    let mut buf = [0u8; 1024];
+    let n = match socket.read(&mut buf).await {
+        Ok(n) => n,
+        Err(_) => return,
+    };
+    let request_line = String::from_utf8_lossy(&buf[..n]);
+
+    let response = if request_line.starts_with("GET /metrics") {
+        let body = render(&handle).await;
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    } else {
+        let body = "not found";
+        format!(
+            "HTTP/1.1 404 Not Found\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    };
+
+    let _ = socket.write_all(response.as_bytes()).await;
+}
+
+/// Render the current gauges and counters in Prometheus text exposition
+/// format. Gauges are sampled fresh on every scrape; counters are read
+/// straight off the atomics the rest of the app increments as work happens.
+async fn render(handle: &MetricsHandle) -> String {
+    let (cpu_usage, total_mem, used_mem) = {
+        let mut sys = SYSTEM.lock().unwrap();
+        sys.refresh_all();
+        (sys.global_cpu_info().cpu_usage(), sys.total_memory(), sys.used_memory())
+    };
+    let memory_usage = if total_mem > 0 {
+        (used_mem as f32 / total_mem as f32) * 100.0
+    } else {
+        0.0
+    };
+    let disk_usage = (PLACEHOLDER_USED_DISK as f32 / PLACEHOLDER_TOTAL_DISK as f32) * 100.0;
+
+    let network_speed = match crate::unified_backend::execute_unified_command(
+        "get_bandwidth_stats",
+        serde_json::json!({}),
+    )
+    .await
+    {
+        Ok(response) if response.success => response
+            .data
+            .and_then(|d| serde_json::from_value::<NetworkSpeed>(d).ok())
+            .unwrap_or(NetworkSpeed { upload: 0, download: 0 }),
+        _ => NetworkSpeed { upload: 0, download: 0 },
+    };
+
+    let mut out = String::new();
+
+    out.push_str("# HELP usenetsync_cpu_usage_percent Current CPU usage.\n");
+    out.push_str("# TYPE usenetsync_cpu_usage_percent gauge\n");
+    out.push_str(&format!("usenetsync_cpu_usage_percent {}\n", cpu_usage));
+
+    out.push_str("# HELP usenetsync_memory_usage_percent Current memory usage.\n");
+    out.push_str("# TYPE usenetsync_memory_usage_percent gauge\n");
+    out.push_str(&format!("usenetsync_memory_usage_percent {}\n", memory_usage));
+
+    out.push_str("# HELP usenetsync_disk_usage_percent Current disk usage.\n");
+    out.push_str("# TYPE usenetsync_disk_usage_percent gauge\n");
+    out.push_str(&format!("usenetsync_disk_usage_percent {}\n", disk_usage));
+
+    out.push_str("# HELP usenetsync_network_speed_bytes Current network throughput.\n");
+    out.push_str("# TYPE usenetsync_network_speed_bytes gauge\n");
+    out.push_str(&format!(
+        "usenetsync_network_speed_bytes{{direction=\"upload\"}} {}\n",
+        network_speed.upload
+    ));
+    out.push_str(&format!(
+        "usenetsync_network_speed_bytes{{direction=\"download\"}} {}\n",
+        network_speed.download
+    ));
+
+    out.push_str("# HELP usenetsync_uploads_queued_total Uploads queued.\n");
+    out.push_str("# TYPE usenetsync_uploads_queued_total counter\n");
+    out.push_str(&format!(
+        "usenetsync_uploads_queued_total {}\n",
+        handle.uploads_queued.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP usenetsync_downloads_started_total Downloads started.\n");
+    out.push_str("# TYPE usenetsync_downloads_started_total counter\n");
+    out.push_str(&format!(
+        "usenetsync_downloads_started_total {}\n",
+        handle.downloads_started.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP usenetsync_shares_created_total Shares created.\n");
+    out.push_str("# TYPE usenetsync_shares_created_total counter\n");
+    out.push_str(&format!(
+        "usenetsync_shares_created_total {}\n",
+        handle.shares_created.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP usenetsync_bytes_transferred_total Bytes transferred across all uploads and downloads.\n");
+    out.push_str("# TYPE usenetsync_bytes_transferred_total counter\n");
+    out.push_str(&format!(
+        "usenetsync_bytes_transferred_total {}\n",
+        handle.bytes_transferred.load(Ordering::Relaxed)
+    ));
+
+    out
+}
@@ -0,0 +1,217 @@
+// Offline license verification
+//
+// TurboActivate normally needs a reachable activation server to vouch for a
+// license. This module adds a path that needs nothing but the binary
+// itself: a license blob signed offline by the vendor with Ed25519, checked
+// here against a public key compiled into the binary. The blob carries the
+// product key, feature tier, expiry, and an optional hardware-id binding;
+// verification enforces the signature, expiry, and (if present) the
+// hardware binding, in that order, so callers can tell exactly why a blob
+// was rejected.
+
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use serde::{Deserialize, Serialize};
+
+/// Vendor Ed25519 public key compiled into the binary. The matching
+/// private key never ships with the app; it lives with whoever issues
+/// license blobs.
+const VENDOR_PUBLIC_KEY: [u8; 32] = [
+    80, 193, 239, 240, 163, 135, 133, 1, 242, 5, 210, 245, 91, 136, 250, 179, 209, 75, 217, 50,
+    183, 165, 176, 94, 26, 53, 21, 238, 115, 243, 123, 199,
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LicenseClaims {
+    pub product_key: String,
+    pub tier: String,
+    pub max_connections: u32,
+    /// Unix timestamp (seconds) after which the license is no longer valid.
+    pub expires_at: i64,
+    /// Hardware id the license is bound to, if any. Unbound licenses pass
+    /// on any machine.
+    pub bound_hardware_id: Option<String>,
+}
+
+/// A `LicenseClaims` plus the vendor signature over its canonical JSON
+/// serialization, as distributed to customers.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SignedLicense {
+    pub claims: LicenseClaims,
+    pub signature: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OfflineLicenseError {
+    Malformed(String),
+    BadSignature,
+    Expired { expired_at: i64 },
+    HardwareMismatch,
+}
+
+impl std::fmt::Display for OfflineLicenseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OfflineLicenseError::Malformed(msg) => write!(f, "malformed license blob: {}", msg),
+            OfflineLicenseError::BadSignature => write!(f, "license signature does not match the vendor key"),
+            OfflineLicenseError::Expired { expired_at } => {
+                write!(f, "license expired at {}", expired_at)
+            }
+            OfflineLicenseError::HardwareMismatch => {
+                write!(f, "license is bound to a different machine")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OfflineLicenseError {}
+
+/// The canonical bytes a signature is computed over: the claims'
+/// JSON serialization. `serde_json` serializes struct fields in
+/// declaration order, so this is stable as long as `LicenseClaims`'s
+/// field order doesn't change.
+fn canonical_claims_bytes(claims: &LicenseClaims) -> Result<Vec<u8>, OfflineLicenseError> {
+    serde_json::to_vec(claims).map_err(|e| OfflineLicenseError::Malformed(e.to_string()))
+}
+
+/// Verify a signed license blob against the compiled-in vendor key,
+/// enforcing expiry and, when the license is hardware-bound, that
+/// `hardware_id` matches. Checks run signature, then expiry, then
+/// hardware binding, so the first failure reported is the most
+/// fundamental one.
+pub fn verify_offline_license(
+    blob: &SignedLicense,
+    now: i64,
+    hardware_id: &str,
+) -> Result<LicenseClaims, OfflineLicenseError> {
+    let public_key = PublicKey::from_bytes(&VENDOR_PUBLIC_KEY)
+        .map_err(|e| OfflineLicenseError::Malformed(e.to_string()))?;
+    let signature = Signature::from_bytes(&blob.signature)
+        .map_err(|_| OfflineLicenseError::BadSignature)?;
+    let message = canonical_claims_bytes(&blob.claims)?;
+
+    public_key
+        .verify(&message, &signature)
+        .map_err(|_| OfflineLicenseError::BadSignature)?;
+
+    if blob.claims.expires_at < now {
+        return Err(OfflineLicenseError::Expired {
+            expired_at: blob.claims.expires_at,
+        });
+    }
+
+    if let Some(bound) = &blob.claims.bound_hardware_id {
+        if bound != hardware_id {
+            return Err(OfflineLicenseError::HardwareMismatch);
+        }
+    }
+
+    Ok(blob.claims.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Keypair, SecretKey, Signer};
+
+    /// Test-only signing key whose public half matches `VENDOR_PUBLIC_KEY`.
+    /// The real private key never lives in this repo.
+    const TEST_SECRET_KEY: [u8; 32] = [
+        225, 109, 101, 198, 135, 236, 123, 84, 114, 178, 113, 179, 125, 184, 134, 56, 2, 213, 44,
+        24, 220, 108, 67, 241, 136, 244, 59, 64, 87, 92, 201, 127,
+    ];
+
+    const OTHER_SECRET_KEY: [u8; 32] = [
+        136, 105, 37, 15, 144, 129, 171, 75, 212, 223, 135, 73, 87, 13, 189, 116, 64, 142, 128,
+        36, 156, 76, 15, 168, 198, 246, 108, 155, 75, 252, 208, 202,
+    ];
+
+    fn keypair_from_secret(secret_bytes: [u8; 32]) -> Keypair {
+        let secret = SecretKey::from_bytes(&secret_bytes).unwrap();
+        let public = (&secret).into();
+        Keypair { secret, public }
+    }
+
+    fn sign_with(keypair: &Keypair, claims: &LicenseClaims) -> SignedLicense {
+        let message = canonical_claims_bytes(claims).unwrap();
+        let signature = keypair.sign(&message);
+        SignedLicense {
+            claims: claims.clone(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    fn sample_claims(bound_hardware_id: Option<String>) -> LicenseClaims {
+        LicenseClaims {
+            product_key: "TEST-KEY-0001".to_string(),
+            tier: "professional".to_string(),
+            max_connections: 30,
+            expires_at: 2_000_000_000,
+            bound_hardware_id,
+        }
+    }
+
+    #[test]
+    fn accepts_a_validly_signed_unbound_license() {
+        let keypair = keypair_from_secret(TEST_SECRET_KEY);
+        let blob = sign_with(&keypair, &sample_claims(None));
+
+        let claims = verify_offline_license(&blob, 1_000_000_000, "any-machine").unwrap();
+        assert_eq!(claims.tier, "professional");
+    }
+
+    #[test]
+    fn accepts_a_matching_hardware_binding() {
+        let keypair = keypair_from_secret(TEST_SECRET_KEY);
+        let blob = sign_with(&keypair, &sample_claims(Some("hw-abc123".to_string())));
+
+        let claims = verify_offline_license(&blob, 1_000_000_000, "hw-abc123").unwrap();
+        assert_eq!(claims.bound_hardware_id.as_deref(), Some("hw-abc123"));
+    }
+
+    #[test]
+    fn rejects_tampered_claims() {
+        let keypair = keypair_from_secret(TEST_SECRET_KEY);
+        let mut blob = sign_with(&keypair, &sample_claims(None));
+        blob.claims.max_connections = 9999;
+
+        assert_eq!(
+            verify_offline_license(&blob, 1_000_000_000, "any-machine"),
+            Err(OfflineLicenseError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn rejects_license_signed_by_wrong_key() {
+        let wrong_keypair = keypair_from_secret(OTHER_SECRET_KEY);
+        let blob = sign_with(&wrong_keypair, &sample_claims(None));
+
+        assert_eq!(
+            verify_offline_license(&blob, 1_000_000_000, "any-machine"),
+            Err(OfflineLicenseError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn rejects_an_expired_license() {
+        let keypair = keypair_from_secret(TEST_SECRET_KEY);
+        let blob = sign_with(&keypair, &sample_claims(None));
+
+        assert_eq!(
+            verify_offline_license(&blob, 3_000_000_000, "any-machine"),
+            Err(OfflineLicenseError::Expired {
+                expired_at: 2_000_000_000
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_a_hardware_mismatch() {
+        let keypair = keypair_from_secret(TEST_SECRET_KEY);
+        let blob = sign_with(&keypair, &sample_claims(Some("hw-abc123".to_string())));
+
+        assert_eq!(
+            verify_offline_license(&blob, 1_000_000_000, "different-machine"),
+            Err(OfflineLicenseError::HardwareMismatch)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+// Offline-first operation log
+//
+// Mutating backend commands are appended to a local append-only journal
+// with a logical (Lamport) timestamp and applied optimistically before the
+// backend has confirmed them. Replaying committed operations in timestamp
+// order is the canonical state; tentative operations sit on top of the
+// last committed state and are folded into it (or rolled back) once the
+// backend responds authoritatively.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::unified_backend::{execute_unified_command, UnifiedResponse};
+
+/// Commands that only read state. These are never queued — if the backend
+/// is unreachable they fail immediately instead of returning stale or
+/// fabricated data.
+const NON_QUEUEABLE: &[&str] = &[
+    "download",
+    "download_share",
+    "get_shares",
+    "get_share_details",
+    "get_folders",
+    "get_user_info",
+    "is_user_initialized",
+    "get_statistics",
+    "check_database_status",
+    "folder_info",
+    "get_authorized_users",
+];
+
+pub fn is_queueable(command: &str) -> bool {
+    !NON_QUEUEABLE.contains(&command)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum OperationStatus {
+    Tentative,
+    Committed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueuedOperation {
+    pub client_id: String,
+    pub logical_ts: u64,
+    pub command: String,
+    pub args: serde_json::Value,
+    pub status: OperationStatus,
+}
+
+struct OperationLog {
+    client_id: String,
+    journal_path: PathBuf,
+    clock: AtomicU64,
+    operations: Mutex<Vec<QueuedOperation>>,
+}
+
+static LOG: Lazy<OperationLog> = Lazy::new(OperationLog::load);
+
+fn journal_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("usenet-sync")
+        .join("operation_log.jsonl")
+}
+
+impl OperationLog {
+    fn load() -> Self {
+        let journal_path = journal_path();
+        let mut operations = Vec::new();
+        let mut max_ts = 0u64;
+
+        if let Ok(file) = fs::File::open(&journal_path) {
+            for line in BufReader::new(file).lines().flatten() {
+                if let Ok(op) = serde_json::from_str::<QueuedOperation>(&line) {
+                    max_ts = max_ts.max(op.logical_ts);
+                    operations.push(op);
+                }
+            }
+        }
+
+        Self {
+            client_id: uuid::Uuid::new_v4().to_string(),
+            journal_path,
+            clock: AtomicU64::new(max_ts),
+            operations: Mutex::new(operations),
+        }
+    }
+
+    fn next_ts(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    fn append(&self, op: &QueuedOperation) {
+        if let Some(parent) = self.journal_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(mut file) = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.journal_path)
+        {
+            if let Ok(line) = serde_json::to_string(op) {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+    }
+
+    /// Rewrite the whole journal after a commit so it never grows unbounded
+    /// with operations that are no longer tentative.
+    fn rewrite(&self) {
+        if let Some(parent) = self.journal_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(mut file) = fs::File::create(&self.journal_path) {
+            for op in self.operations.lock().unwrap().iter() {
+                if let Ok(line) = serde_json::to_string(op) {
+                    let _ = writeln!(file, "{}", line);
+                }
+            }
+        }
+    }
+}
+
+/// Execute a mutating command offline-first: try the backend immediately,
+/// and if it's unreachable, journal the operation as tentative and return
+/// an optimistic response instead of failing outright. Read-only commands
+/// bypass the journal entirely and fail fast.
+pub async fn execute_offline_first(
+    command: &str,
+    args: serde_json::Value,
+) -> Result<UnifiedResponse, String> {
+    if !is_queueable(command) {
+        return execute_unified_command(command, args).await;
+    }
+
+    match execute_unified_command(command, args.clone()).await {
+        Ok(response) => Ok(response),
+        Err(backend_err) => {
+            let op = QueuedOperation {
+                client_id: LOG.client_id.clone(),
+                logical_ts: LOG.next_ts(),
+                command: command.to_string(),
+                args,
+                status: OperationStatus::Tentative,
+            };
+            LOG.append(&op);
+            LOG.operations.lock().unwrap().push(op);
+
+            Ok(UnifiedResponse {
+                id: 0,
+                success: true,
+                data: Some(serde_json::json!({ "tentative": true, "reason": backend_err })),
+                error: None,
+            })
+        }
+    }
+}
+
+/// Operations not yet confirmed by the backend, oldest first (ties broken
+/// by client id for a stable, deterministic replay order).
+pub fn pending_operations() -> Vec<QueuedOperation> {
+    let mut ops: Vec<QueuedOperation> = LOG
+        .operations
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|op| op.status == OperationStatus::Tentative)
+        .cloned()
+        .collect();
+    ops.sort_by(|a, b| {
+        a.logical_ts
+            .cmp(&b.logical_ts)
+            .then_with(|| a.client_id.cmp(&b.client_id))
+    });
+    ops
+}
+
+/// Flush the journal to the backend in logical-timestamp order, folding
+/// each authoritative response into committed state: an accepted operation
+/// is committed, a refused one is rolled back out of the journal. Stops at
+/// the first operation that still can't reach the backend so ordering is
+/// preserved for the next sync attempt.
+pub async fn sync() -> Result<(), String> {
+    for op in pending_operations() {
+        match execute_unified_command(&op.command, op.args.clone()).await {
+            Ok(response) if response.success => {
+                {
+                    let mut operations = LOG.operations.lock().unwrap();
+                    if let Some(entry) = operations
+                        .iter_mut()
+                        .find(|o| o.client_id == op.client_id && o.logical_ts == op.logical_ts)
+                    {
+                        entry.status = OperationStatus::Committed;
+                    }
+                }
+                LOG.rewrite();
+            }
+            Ok(_) => {
+                LOG.operations
+                    .lock()
+                    .unwrap()
+                    .retain(|o| !(o.client_id == op.client_id && o.logical_ts == op.logical_ts));
+                LOG.rewrite();
+            }
+            Err(_) => break,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,517 @@
+// SASL client mechanisms for authenticating backend/NNTP credentials.
+//
+// Usenet providers that advertise `AUTHINFO SASL` expect one of a handful
+// of mechanisms. This module implements the client side of PLAIN, LOGIN,
+// CRAM-MD5, and SCRAM-SHA-256 as small step-driven state machines: given
+// the server's advertised mechanism list, `negotiate` picks the strongest
+// mutually supported one, and the resulting client consumes server
+// challenges and produces responses until the exchange completes.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+type HmacMd5 = Hmac<Md5>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mechanism {
+    Plain,
+    Login,
+    CramMd5,
+    ScramSha256,
+}
+
+impl Mechanism {
+    pub fn name(self) -> &'static str {
+        match self {
+            Mechanism::Plain => "PLAIN",
+            Mechanism::Login => "LOGIN",
+            Mechanism::CramMd5 => "CRAM-MD5",
+            Mechanism::ScramSha256 => "SCRAM-SHA-256",
+        }
+    }
+}
+
+/// Mechanisms in strongest-first order, used to pick the best mutually
+/// supported option.
+const PREFERENCE_ORDER: &[Mechanism] = &[
+    Mechanism::ScramSha256,
+    Mechanism::CramMd5,
+    Mechanism::Login,
+    Mechanism::Plain,
+];
+
+/// Pick the strongest mechanism this module implements that also appears in
+/// the server's advertised list, case-insensitively.
+pub fn negotiate(server_mechanisms: &[String]) -> Option<Mechanism> {
+    PREFERENCE_ORDER
+        .iter()
+        .copied()
+        .find(|mech| server_mechanisms.iter().any(|m| m.eq_ignore_ascii_case(mech.name())))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SaslError {
+    UnexpectedChallenge,
+    Malformed(String),
+    ServerVerificationFailed,
+}
+
+impl std::fmt::Display for SaslError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaslError::UnexpectedChallenge => write!(f, "received a challenge in an unexpected state"),
+            SaslError::Malformed(msg) => write!(f, "malformed SASL message: {}", msg),
+            SaslError::ServerVerificationFailed => write!(f, "server signature did not verify"),
+        }
+    }
+}
+
+impl std::error::Error for SaslError {}
+
+/// Outcome of feeding a server challenge into a [`SaslClient`].
+pub enum StepResult {
+    /// Send this response back to the server.
+    Respond(Vec<u8>),
+    /// The exchange is complete; nothing further to send.
+    Done,
+}
+
+/// Client side of one SASL mechanism, driven challenge-by-challenge.
+pub trait SaslClient {
+    /// The client-first message to send before any server challenge, if
+    /// the mechanism has one (PLAIN, SCRAM). Mechanisms that wait for the
+    /// server to speak first (LOGIN, CRAM-MD5) return `None`.
+    fn initial_response(&mut self) -> Option<Vec<u8>>;
+
+    /// Consume one server challenge and produce the next step.
+    fn step(&mut self, challenge: &[u8]) -> Result<StepResult, SaslError>;
+
+    fn is_complete(&self) -> bool;
+}
+
+/// Construct the client side of `mechanism` for the given credentials.
+pub fn client_for(mechanism: Mechanism, username: &str, password: &str) -> Box<dyn SaslClient> {
+    match mechanism {
+        Mechanism::Plain => Box::new(PlainClient::new(username, password)),
+        Mechanism::Login => Box::new(LoginClient::new(username, password)),
+        Mechanism::CramMd5 => Box::new(CramMd5Client::new(username, password)),
+        Mechanism::ScramSha256 => Box::new(ScramSha256Client::new_with_random_nonce(username, password)),
+    }
+}
+
+/// Negotiate the strongest of `server_mechanisms` this module supports and
+/// produce its client-first message, base64-encoded the way `AUTHINFO
+/// SASL` expects it on the wire. Returns `None` if none of
+/// `server_mechanisms` match, or if the chosen mechanism has no
+/// client-first message of its own (LOGIN/CRAM-MD5 wait for the server to
+/// send a challenge first, which requires an actual connection to the
+/// server -- callers without one should negotiate against `["PLAIN"]`,
+/// which always produces a complete, self-contained exchange).
+pub fn client_first_message(
+    server_mechanisms: &[String],
+    username: &str,
+    password: &str,
+) -> Option<(Mechanism, String)> {
+    let mechanism = negotiate(server_mechanisms)?;
+    let initial = client_for(mechanism, username, password).initial_response()?;
+    Some((mechanism, BASE64.encode(initial)))
+}
+
+// ---------------------------------------------------------------------
+// PLAIN (RFC 4616)
+// ---------------------------------------------------------------------
+
+pub struct PlainClient {
+    username: String,
+    password: String,
+    done: bool,
+}
+
+impl PlainClient {
+    pub fn new(username: &str, password: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            password: password.to_string(),
+            done: false,
+        }
+    }
+}
+
+impl SaslClient for PlainClient {
+    fn initial_response(&mut self) -> Option<Vec<u8>> {
+        self.done = true;
+        let mut msg = Vec::new();
+        msg.push(0); // authzid omitted
+        msg.extend_from_slice(self.username.as_bytes());
+        msg.push(0);
+        msg.extend_from_slice(self.password.as_bytes());
+        Some(msg)
+    }
+
+    fn step(&mut self, _challenge: &[u8]) -> Result<StepResult, SaslError> {
+        Ok(StepResult::Done)
+    }
+
+    fn is_complete(&self) -> bool {
+        self.done
+    }
+}
+
+// ---------------------------------------------------------------------
+// LOGIN (informal de facto mechanism: "Username:" / "Password:" prompts)
+// ---------------------------------------------------------------------
+
+pub struct LoginClient {
+    username: String,
+    password: String,
+    step: u8,
+    done: bool,
+}
+
+impl LoginClient {
+    pub fn new(username: &str, password: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            password: password.to_string(),
+            step: 0,
+            done: false,
+        }
+    }
+}
+
+impl SaslClient for LoginClient {
+    fn initial_response(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn step(&mut self, _challenge: &[u8]) -> Result<StepResult, SaslError> {
+        self.step += 1;
+        match self.step {
+            1 => Ok(StepResult::Respond(self.username.clone().into_bytes())),
+            2 => {
+                self.done = true;
+                Ok(StepResult::Respond(self.password.clone().into_bytes()))
+            }
+            _ => Err(SaslError::UnexpectedChallenge),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.done
+    }
+}
+
+// ---------------------------------------------------------------------
+// CRAM-MD5 (RFC 2195)
+// ---------------------------------------------------------------------
+
+pub struct CramMd5Client {
+    username: String,
+    password: String,
+    done: bool,
+}
+
+impl CramMd5Client {
+    pub fn new(username: &str, password: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            password: password.to_string(),
+            done: false,
+        }
+    }
+}
+
+impl SaslClient for CramMd5Client {
+    fn initial_response(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn step(&mut self, challenge: &[u8]) -> Result<StepResult, SaslError> {
+        if self.done {
+            return Err(SaslError::UnexpectedChallenge);
+        }
+
+        let mut mac = HmacMd5::new_from_slice(self.password.as_bytes())
+            .expect("HMAC accepts a key of any length");
+        mac.update(challenge);
+        let digest = hex::encode(mac.finalize().into_bytes());
+
+        self.done = true;
+        Ok(StepResult::Respond(format!("{} {}", self.username, digest).into_bytes()))
+    }
+
+    fn is_complete(&self) -> bool {
+        self.done
+    }
+}
+
+// ---------------------------------------------------------------------
+// SCRAM-SHA-256 (RFC 5802 / RFC 7677)
+// ---------------------------------------------------------------------
+
+enum ScramStage {
+    ClientFirstSent,
+    ClientFinalSent,
+    Done,
+}
+
+pub struct ScramSha256Client {
+    username: String,
+    password: String,
+    client_nonce: String,
+    client_first_bare: String,
+    salted_password: Vec<u8>,
+    auth_message: String,
+    stage: ScramStage,
+}
+
+impl ScramSha256Client {
+    pub fn new(username: &str, password: &str, client_nonce: impl Into<String>) -> Self {
+        Self {
+            username: username.to_string(),
+            password: password.to_string(),
+            client_nonce: client_nonce.into(),
+            client_first_bare: String::new(),
+            salted_password: Vec::new(),
+            auth_message: String::new(),
+            stage: ScramStage::ClientFirstSent,
+        }
+    }
+
+    pub fn new_with_random_nonce(username: &str, password: &str) -> Self {
+        use rand::RngCore;
+        let mut raw = [0u8; 18];
+        rand::rngs::OsRng.fill_bytes(&mut raw);
+        Self::new(username, password, BASE64.encode(raw))
+    }
+}
+
+/// RFC 5802 `Hi(str, salt, i)`: PBKDF2-HMAC-SHA-256 specialized to exactly
+/// one block, which is all that's needed since SHA-256's output length
+/// already matches the desired derived-key length.
+fn hi(password: &[u8], salt: &[u8], iterations: u32) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(password).expect("HMAC accepts a key of any length");
+    mac.update(salt);
+    mac.update(&1u32.to_be_bytes());
+    let mut u = mac.finalize().into_bytes().to_vec();
+    let mut result = u.clone();
+
+    for _ in 1..iterations {
+        let mut mac = HmacSha256::new_from_slice(password).expect("HMAC accepts a key of any length");
+        mac.update(&u);
+        u = mac.finalize().into_bytes().to_vec();
+        for (r, b) in result.iter_mut().zip(u.iter()) {
+            *r ^= b;
+        }
+    }
+
+    result
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts a key of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn parse_scram_fields(message: &str) -> std::collections::HashMap<char, String> {
+    message
+        .split(',')
+        .filter_map(|field| {
+            let mut parts = field.splitn(2, '=');
+            let key = parts.next()?.chars().next()?;
+            let value = parts.next()?.to_string();
+            Some((key, value))
+        })
+        .collect()
+}
+
+impl SaslClient for ScramSha256Client {
+    fn initial_response(&mut self) -> Option<Vec<u8>> {
+        self.client_first_bare = format!("n={},r={}", self.username, self.client_nonce);
+        let gs2_header = "n,,";
+        Some(format!("{}{}", gs2_header, self.client_first_bare).into_bytes())
+    }
+
+    fn step(&mut self, challenge: &[u8]) -> Result<StepResult, SaslError> {
+        let message = std::str::from_utf8(challenge)
+            .map_err(|_| SaslError::Malformed("challenge is not valid UTF-8".to_string()))?;
+
+        match self.stage {
+            ScramStage::ClientFirstSent => {
+                let fields = parse_scram_fields(message);
+                let server_nonce = fields
+                    .get(&'r')
+                    .ok_or_else(|| SaslError::Malformed("missing r= in server-first-message".to_string()))?;
+                if !server_nonce.starts_with(&self.client_nonce) {
+                    return Err(SaslError::Malformed("server nonce does not extend client nonce".to_string()));
+                }
+                let salt_b64 = fields
+                    .get(&'s')
+                    .ok_or_else(|| SaslError::Malformed("missing s= in server-first-message".to_string()))?;
+                let salt = BASE64
+                    .decode(salt_b64)
+                    .map_err(|_| SaslError::Malformed("invalid base64 salt".to_string()))?;
+                let iterations: u32 = fields
+                    .get(&'i')
+                    .ok_or_else(|| SaslError::Malformed("missing i= in server-first-message".to_string()))?
+                    .parse()
+                    .map_err(|_| SaslError::Malformed("invalid iteration count".to_string()))?;
+
+                self.salted_password = hi(self.password.as_bytes(), &salt, iterations);
+
+                let channel_binding = BASE64.encode("n,,");
+                let client_final_without_proof = format!("c={},r={}", channel_binding, server_nonce);
+                self.auth_message = format!(
+                    "{},{},{}",
+                    self.client_first_bare, message, client_final_without_proof
+                );
+
+                let client_key = hmac_sha256(&self.salted_password, b"Client Key");
+                let stored_key = Sha256::digest(&client_key).to_vec();
+                let client_signature = hmac_sha256(&stored_key, self.auth_message.as_bytes());
+                let client_proof: Vec<u8> = client_key
+                    .iter()
+                    .zip(client_signature.iter())
+                    .map(|(k, s)| k ^ s)
+                    .collect();
+
+                let response = format!(
+                    "{},p={}",
+                    client_final_without_proof,
+                    BASE64.encode(client_proof)
+                );
+
+                self.stage = ScramStage::ClientFinalSent;
+                Ok(StepResult::Respond(response.into_bytes()))
+            }
+            ScramStage::ClientFinalSent => {
+                let fields = parse_scram_fields(message);
+                let server_signature_b64 = fields
+                    .get(&'v')
+                    .ok_or_else(|| SaslError::Malformed("missing v= in server-final-message".to_string()))?;
+                let received = BASE64
+                    .decode(server_signature_b64)
+                    .map_err(|_| SaslError::Malformed("invalid base64 server signature".to_string()))?;
+
+                let server_key = hmac_sha256(&self.salted_password, b"Server Key");
+                let expected = hmac_sha256(&server_key, self.auth_message.as_bytes());
+
+                if expected != received {
+                    return Err(SaslError::ServerVerificationFailed);
+                }
+
+                self.stage = ScramStage::Done;
+                Ok(StepResult::Done)
+            }
+            ScramStage::Done => Err(SaslError::UnexpectedChallenge),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        matches!(self.stage, ScramStage::Done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiate_picks_strongest_mutual_mechanism() {
+        let offered = vec!["PLAIN".to_string(), "LOGIN".to_string(), "CRAM-MD5".to_string()];
+        assert_eq!(negotiate(&offered), Some(Mechanism::CramMd5));
+
+        let offered = vec!["plain".to_string()];
+        assert_eq!(negotiate(&offered), Some(Mechanism::Plain));
+
+        let offered = vec!["GSSAPI".to_string()];
+        assert_eq!(negotiate(&offered), None);
+    }
+
+    #[test]
+    fn plain_initial_response_matches_rfc4616_layout() {
+        let mut client = PlainClient::new("tim", "tanstaaftanstaaf");
+        let response = client.initial_response().unwrap();
+        assert_eq!(response, b"\0tim\0tanstaaftanstaaf");
+        assert!(client.is_complete());
+    }
+
+    #[test]
+    fn login_responds_username_then_password() {
+        let mut client = LoginClient::new("tim", "secret");
+        assert!(!client.is_complete());
+        match client.step(b"Username:").unwrap() {
+            StepResult::Respond(msg) => assert_eq!(msg, b"tim"),
+            _ => panic!("expected a response"),
+        }
+        assert!(!client.is_complete());
+        match client.step(b"Password:").unwrap() {
+            StepResult::Respond(msg) => assert_eq!(msg, b"secret"),
+            _ => panic!("expected a response"),
+        }
+        assert!(client.is_complete());
+    }
+
+    #[test]
+    fn cram_md5_matches_rfc2195_test_vector() {
+        // RFC 2195, section 3.
+        let mut client = CramMd5Client::new("tim", "tanstaaftanstaaf");
+        let challenge = b"<1896.697170952@postoffice.reston.mci.net>";
+        match client.step(challenge).unwrap() {
+            StepResult::Respond(msg) => {
+                assert_eq!(
+                    msg,
+                    b"tim b913a602c7eda7a495b4e6e7334d3890".to_vec()
+                );
+            }
+            _ => panic!("expected a response"),
+        }
+        assert!(client.is_complete());
+    }
+
+    #[test]
+    fn scram_sha256_matches_rfc7677_test_vector() {
+        // RFC 7677, section 3.
+        let mut client = ScramSha256Client::new("user", "pencil", "rOprNGfwEbeRWgbNEkqO");
+
+        let client_first = client.initial_response().unwrap();
+        assert_eq!(
+            client_first,
+            b"n,,n=user,r=rOprNGfwEbeRWgbNEkqO".to_vec()
+        );
+
+        let server_first = b"r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+        let client_final = match client.step(server_first).unwrap() {
+            StepResult::Respond(msg) => msg,
+            _ => panic!("expected a response"),
+        };
+        let client_final = String::from_utf8(client_final).unwrap();
+        assert_eq!(
+            client_final,
+            "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+        );
+
+        let server_final = b"v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+        match client.step(server_final).unwrap() {
+            StepResult::Done => {}
+            _ => panic!("expected the exchange to complete"),
+        }
+        assert!(client.is_complete());
+    }
+
+    #[test]
+    fn scram_sha256_rejects_forged_server_signature() {
+        let mut client = ScramSha256Client::new("user", "pencil", "rOprNGfwEbeRWgbNEkqO");
+        client.initial_response();
+        let server_first = b"r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+        client.step(server_first).unwrap();
+
+        let forged = b"v=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+        assert_eq!(client.step(forged), Err(SaslError::ServerVerificationFailed));
+    }
+}
@@ -0,0 +1,191 @@
+// Share code codec
+//
+// Encodes a share/folder's raw binary identifier into a short, grouped code
+// that is easy to read aloud and retype, the way device-commissioning
+// protocols turn a pairing secret into a human-typeable setup code.
+//
+// Alphabet: 0-9A-Z minus the visually ambiguous I O Q S Z, plus - and .,
+// for 33 symbols. Bytes are encoded in fixed-size groups (3 bytes -> 5
+// chars, 2 bytes -> 4 chars, 1 byte -> 2 chars), each group read
+// little-endian and left-padded with the alphabet's zero symbol ('0') so
+// every group has a fixed width regardless of value. A single trailing
+// checksum character guards against transcription errors.
+
+const ALPHABET: &[u8] = b"0123456789ABCDEFGHJKLMNPRTUVWXY-.";
+const BASE: u32 = ALPHABET.len() as u32;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ShareCodeError {
+    /// The code's length doesn't correspond to any valid grouping of bytes.
+    InvalidLength,
+    /// A character outside the codec's alphabet was encountered.
+    InvalidCharacter(char),
+    /// The trailing checksum character didn't match the body.
+    ChecksumMismatch,
+}
+
+impl std::fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareCodeError::InvalidLength => write!(f, "share code has an invalid length"),
+            ShareCodeError::InvalidCharacter(c) => {
+                write!(f, "share code contains invalid character '{}'", c)
+            }
+            ShareCodeError::ChecksumMismatch => write!(f, "share code checksum does not match"),
+        }
+    }
+}
+
+impl std::error::Error for ShareCodeError {}
+
+fn digit_value(c: char) -> Result<u32, ShareCodeError> {
+    ALPHABET
+        .iter()
+        .position(|&a| a as char == c)
+        .map(|pos| pos as u32)
+        .ok_or(ShareCodeError::InvalidCharacter(c))
+}
+
+/// Encode one group of up to 3 bytes into `out_len` alphabet characters.
+fn encode_group(bytes: &[u8], out_len: usize) -> String {
+    let mut value: u64 = 0;
+    for (i, b) in bytes.iter().enumerate() {
+        value |= (*b as u64) << (8 * i);
+    }
+
+    let mut digits = vec![0u32; out_len];
+    for slot in digits.iter_mut() {
+        *slot = (value % BASE as u64) as u32;
+        value /= BASE as u64;
+    }
+    digits.reverse();
+
+    digits
+        .into_iter()
+        .map(|d| ALPHABET[d as usize] as char)
+        .collect()
+}
+
+/// Decode one group of characters back into `byte_len` bytes.
+fn decode_group(chars: &[char], byte_len: usize) -> Result<Vec<u8>, ShareCodeError> {
+    let mut value: u64 = 0;
+    for &c in chars {
+        value = value * BASE as u64 + digit_value(c)? as u64;
+    }
+
+    let mut bytes = Vec::with_capacity(byte_len);
+    for i in 0..byte_len {
+        bytes.push(((value >> (8 * i)) & 0xFF) as u8);
+    }
+    Ok(bytes)
+}
+
+fn checksum_char(body: &str) -> Result<char, ShareCodeError> {
+    let mut sum: u32 = 0;
+    for c in body.chars() {
+        sum = (sum + digit_value(c)?) % BASE;
+    }
+    Ok(ALPHABET[sum as usize] as char)
+}
+
+/// Encode raw bytes (e.g. a share or folder id) into a transcription-friendly
+/// share code with a trailing checksum character.
+pub fn encode_share_code(data: &[u8]) -> String {
+    let mut body = String::new();
+
+    for chunk in data.chunks(3) {
+        let out_len = match chunk.len() {
+            3 => 5,
+            2 => 4,
+            1 => 2,
+            _ => unreachable!("chunks(3) never yields an empty or >3-byte slice"),
+        };
+        body.push_str(&encode_group(chunk, out_len));
+    }
+
+    // checksum_char only fails on characters outside our own alphabet, which
+    // encode_group can never produce.
+    let checksum = checksum_char(&body).expect("encoded body uses only alphabet characters");
+    body.push(checksum);
+    body
+}
+
+/// Decode a share code produced by [`encode_share_code`] back into its raw
+/// bytes, rejecting codes with a bad length, unknown characters, or a
+/// mismatched checksum.
+pub fn decode_share_code(code: &str) -> Result<Vec<u8>, ShareCodeError> {
+    let chars: Vec<char> = code.chars().collect();
+    if chars.is_empty() {
+        return Err(ShareCodeError::InvalidLength);
+    }
+
+    let (body_chars, checksum) = chars.split_at(chars.len() - 1);
+    let body: String = body_chars.iter().collect();
+
+    if checksum_char(&body)? != checksum[0] {
+        return Err(ShareCodeError::ChecksumMismatch);
+    }
+
+    let mut bytes = Vec::new();
+    let mut i = 0;
+    while i < body_chars.len() {
+        let remaining = body_chars.len() - i;
+        let (group_len, byte_len) = match remaining {
+            r if r >= 5 => (5, 3),
+            4 => (4, 2),
+            2 => (2, 1),
+            _ => return Err(ShareCodeError::InvalidLength),
+        };
+        bytes.extend(decode_group(&body_chars[i..i + group_len], byte_len)?);
+        i += group_len;
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_various_lengths() {
+        for len in 0..16 {
+            let data: Vec<u8> = (0..len as u8).map(|b| b.wrapping_mul(37).wrapping_add(5)).collect();
+            let code = encode_share_code(&data);
+            let decoded = decode_share_code(&code).expect("valid code should decode");
+            assert_eq!(decoded, data, "round trip failed for length {}", len);
+        }
+    }
+
+    #[test]
+    fn pads_short_groups_with_zero_symbol() {
+        let code = encode_share_code(&[0]);
+        // 1 byte of zero value should encode to two zero symbols plus checksum.
+        assert_eq!(&code[..2], "00");
+    }
+
+    #[test]
+    fn rejects_tampered_checksum() {
+        let mut code = encode_share_code(b"usenetsync-share");
+        let last = code.pop().unwrap();
+        // Flip the checksum character to something else in the alphabet.
+        let replacement = if last == '0' { '1' } else { '0' };
+        code.push(replacement);
+
+        assert_eq!(decode_share_code(&code), Err(ShareCodeError::ChecksumMismatch));
+    }
+
+    #[test]
+    fn rejects_invalid_characters() {
+        assert_eq!(
+            decode_share_code("I0"),
+            Err(ShareCodeError::InvalidCharacter('I'))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_length() {
+        // Body length 1 (plus its checksum char) can't correspond to any group.
+        assert_eq!(decode_share_code("00"), Err(ShareCodeError::InvalidLength));
+    }
+}
@@ -0,0 +1,122 @@
+// Background system-stats sampler.
+//
+// `get_system_stats` used to return 0 for everything that isn't a true
+// instantaneous snapshot -- network throughput only means something as a
+// rate between two points in time, and active-transfer/share counts need
+// someone actually counting. This spawns a loop (same spawn-once-and-tick-
+// forever shape as `feeds::watch`) that samples host CPU/memory/disk and
+// the cumulative upload/download byte counters once a second, diffs the
+// byte counters against the previous tick to get bytes/sec, reads the live
+// transfer/share counts, and stores the result behind a `Mutex` that
+// `get_system_stats` just reads back instead of re-polling -- emitting the
+// same snapshot as a `"system-stats"` event so the frontend gets a
+// real-time feed instead of having to ask.
+
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use tauri::{Emitter, Manager};
+
+use crate::commands::system::MetricsHandle;
+use crate::{AppState, NetworkSpeed, SystemStats};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+static LATEST: Lazy<Mutex<SystemStats>> = Lazy::new(|| {
+    Mutex::new(SystemStats {
+        cpu_usage: 0.0,
+        memory_usage: 0.0,
+        disk_usage: 0.0,
+        network_speed: NetworkSpeed { upload: 0.0, download: 0.0 },
+        active_transfers: 0,
+        total_shares: 0,
+    })
+});
+
+/// The most recently sampled snapshot. `get_system_stats` reads this back
+/// rather than re-running `sysinfo` on every call.
+pub fn latest() -> SystemStats {
+    LATEST.lock().unwrap().clone()
+}
+
+/// Sample host/network/transfer state once a second forever, updating
+/// [`latest`] and emitting it as a `"system-stats"` event. Intended to be
+/// spawned once at startup from `tauri::Builder::setup`, same as
+/// `feeds::watch`.
+pub async fn watch(app_handle: tauri::AppHandle, metrics: MetricsHandle) {
+    let mut ticker = tokio::time::interval(SAMPLE_INTERVAL);
+    let mut last_uploaded = metrics.bytes_uploaded.load(Ordering::Relaxed);
+    let mut last_downloaded = metrics.bytes_downloaded.load(Ordering::Relaxed);
+    // Kept across ticks (and refreshed, never recreated) so CPU usage has
+    // a delta to measure from -- sysinfo needs two refreshes spread over
+    // time to compute it; a fresh System every tick always reads ~0%.
+    let mut sys = sysinfo::System::new_all();
+
+    loop {
+        ticker.tick().await;
+
+        let uploaded = metrics.bytes_uploaded.load(Ordering::Relaxed);
+        let downloaded = metrics.bytes_downloaded.load(Ordering::Relaxed);
+        let upload_rate = uploaded.saturating_sub(last_uploaded) as f64 / SAMPLE_INTERVAL.as_secs_f64();
+        let download_rate = downloaded.saturating_sub(last_downloaded) as f64 / SAMPLE_INTERVAL.as_secs_f64();
+        last_uploaded = uploaded;
+        last_downloaded = downloaded;
+
+        let (cpu_usage, memory_usage, disk_usage) = sample_host(&mut sys);
+
+        let active_transfers = app_handle
+            .state::<AppState>()
+            .transfers
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|t| t.status == "active")
+            .count() as u32;
+
+        let total_shares = crate::current_share_count().await.unwrap_or(0) as u32;
+
+        let snapshot = SystemStats {
+            cpu_usage,
+            memory_usage,
+            disk_usage,
+            network_speed: NetworkSpeed { upload: upload_rate, download: download_rate },
+            active_transfers,
+            total_shares,
+        };
+
+        *LATEST.lock().unwrap() = snapshot.clone();
+        let _ = app_handle.emit("system-stats", &snapshot);
+    }
+}
+
+fn sample_host(sys: &mut sysinfo::System) -> (f32, f32, f32) {
+    use sysinfo::Disks;
+
+    sys.refresh_all();
+
+    let cpu_usage = sys.global_cpu_info().cpu_usage();
+    let memory_usage = if sys.total_memory() > 0 {
+        (sys.used_memory() as f32 / sys.total_memory() as f32) * 100.0
+    } else {
+        0.0
+    };
+
+    let disks = Disks::new_with_refreshed_list();
+    let disk_usage = disks
+        .list()
+        .iter()
+        .map(|disk| {
+            if disk.total_space() > 0 {
+                let used = disk.total_space() - disk.available_space();
+                (used as f32 / disk.total_space() as f32) * 100.0
+            } else {
+                0.0
+            }
+        })
+        .next()
+        .unwrap_or(0.0);
+
+    (cpu_usage, memory_usage, disk_usage)
+}
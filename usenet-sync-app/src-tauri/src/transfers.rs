@@ -0,0 +1,172 @@
+// Persisted transfer state backing `pause_transfer`/`resume_transfer`/
+// `cancel_transfer`.
+//
+// The actual posting/fetching loop runs inside the opaque backend process,
+// not here -- there's no Rust-side segment loop for an `AtomicU8` check to
+// interrupt directly. What Rust can honestly own is the canonical "should
+// this transfer keep going" flag and a record of its progress: the flag is
+// relayed to the backend as a control command (which it checks between its
+// own segments, the same way `BackendPool` relays reads rather than owning
+// real sockets), and the record is written to disk so an interrupted
+// transfer can be reloaded and offered for resume after a restart instead
+// of silently vanishing with the in-memory `transfers` map.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The engine should keep posting/fetching segments.
+pub const RUNNING: u8 = 0;
+/// The engine should stop after its current segment and wait.
+pub const PAUSED: u8 = 1;
+/// The engine should stop and tear down the connection.
+pub const CANCELLED: u8 = 2;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferRecord {
+    pub id: String,
+    #[serde(rename = "folderId")]
+    pub folder_id: String,
+    pub direction: String,
+    #[serde(rename = "totalSize")]
+    pub total_size: u64,
+    #[serde(rename = "byteOffset")]
+    pub byte_offset: u64,
+    #[serde(rename = "segmentIndex")]
+    pub segment_index: u32,
+    pub status: String,
+}
+
+struct TransferStore {
+    path: PathBuf,
+    records: Mutex<Vec<TransferRecord>>,
+    controls: Mutex<HashMap<String, Arc<AtomicU8>>>,
+}
+
+static STORE: Lazy<TransferStore> = Lazy::new(TransferStore::load);
+
+fn store_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("usenet-sync")
+        .join("transfers.json")
+}
+
+impl TransferStore {
+    fn load() -> Self {
+        let path = store_path();
+        let records: Vec<TransferRecord> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        // Control flags aren't persisted -- they only make sense for a
+        // live process -- but a reloaded record that was still running
+        // when the app last exited gets a fresh RUNNING flag so it can be
+        // resumed the same way an in-session transfer would be.
+        let controls = records
+            .iter()
+            .map(|r| (r.id.clone(), Arc::new(AtomicU8::new(RUNNING))))
+            .collect();
+
+        Self { path, records: Mutex::new(records), controls: Mutex::new(controls) }
+    }
+
+    fn save(&self) {
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&*self.records.lock().unwrap()) {
+            let _ = fs::write(&self.path, json);
+        }
+    }
+}
+
+/// Register a newly started transfer: creates its persisted record and
+/// control flag, returning the flag for the caller to thread through to
+/// the backend.
+pub fn start(id: String, folder_id: String, direction: &str, total_size: u64) -> Arc<AtomicU8> {
+    let control = Arc::new(AtomicU8::new(RUNNING));
+    STORE.controls.lock().unwrap().insert(id.clone(), control.clone());
+    STORE.records.lock().unwrap().push(TransferRecord {
+        id,
+        folder_id,
+        direction: direction.to_string(),
+        total_size,
+        byte_offset: 0,
+        segment_index: 0,
+        status: "active".to_string(),
+    });
+    STORE.save();
+    control
+}
+
+/// The control flag for `id`, if it still has one (cancelled/completed
+/// transfers are dropped from the control table once removed).
+pub fn control(id: &str) -> Option<Arc<AtomicU8>> {
+    STORE.controls.lock().unwrap().get(id).cloned()
+}
+
+/// Update `id`'s status and persist it. Returns the updated record for the
+/// caller to emit as a Tauri event, or `None` if `id` isn't known.
+pub fn set_status(id: &str, status: &str) -> Option<TransferRecord> {
+    let mut records = STORE.records.lock().unwrap();
+    let record = records.iter_mut().find(|r| r.id == id)?;
+    record.status = status.to_string();
+    let updated = record.clone();
+    drop(records);
+    STORE.save();
+    Some(updated)
+}
+
+/// Update `id`'s progress (byte offset and segment index) as the backend
+/// reports it.
+pub fn update_progress(id: &str, byte_offset: u64, segment_index: u32) {
+    let mut records = STORE.records.lock().unwrap();
+    if let Some(record) = records.iter_mut().find(|r| r.id == id) {
+        record.byte_offset = byte_offset;
+        record.segment_index = segment_index;
+    }
+    drop(records);
+    STORE.save();
+}
+
+/// Drop `id`'s record and control flag entirely, e.g. after cancellation.
+pub fn remove(id: &str) {
+    STORE.records.lock().unwrap().retain(|r| r.id != id);
+    STORE.controls.lock().unwrap().remove(id);
+    STORE.save();
+}
+
+/// Transfers that were neither completed, failed, nor cancelled as of the
+/// last save -- candidates to reload and offer to resume at startup.
+pub fn resumable() -> Vec<TransferRecord> {
+    STORE
+        .records
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|r| r.status == "active" || r.status == "paused")
+        .cloned()
+        .collect()
+}
+
+fn state_for(status: &str) -> u8 {
+    match status {
+        "paused" => PAUSED,
+        "cancelled" => CANCELLED,
+        _ => RUNNING,
+    }
+}
+
+/// Set `id`'s control flag to match `status` ("active"/"paused"/
+/// "cancelled"), if it has one.
+pub fn set_control(id: &str, status: &str) {
+    if let Some(control) = control(id) {
+        control.store(state_for(status), Ordering::SeqCst);
+    }
+}
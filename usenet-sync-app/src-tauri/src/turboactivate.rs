@@ -140,33 +140,31 @@ impl TurboActivate {
         }
     }
     
+    /// Collect the current machine's multi-signal fingerprint (CPU brand,
+    /// each NIC MAC, bucketed memory tier, disk identifiers, machine id),
+    /// hashed component by component so a bound license can tolerate
+    /// bounded hardware drift instead of invalidating on any change. See
+    /// [`verify_hardware_fingerprint`] for the M-of-N acceptance check.
+    pub fn get_hardware_fingerprint(&self) -> crate::hardware_fingerprint::HardwareFingerprint {
+        crate::hardware_fingerprint::current_fingerprint()
+    }
+
+    /// A single opaque hardware id, derived from the full fingerprint, for
+    /// callers (like [`LicenseStatus`]) that just want something to
+    /// display rather than compare component-by-component.
     pub fn get_hardware_id(&self) -> Result<String, String> {
-        // Use system info to generate hardware ID
-        use sysinfo::System;
-        let sys = System::new_all();
-        
-        let cpu_info = sys.cpus().first()
-            .map(|_cpu| "GenericCPU")
-            .unwrap_or("Unknown");
-        
-        let mac_address = mac_address::get_mac_address()
-            .ok()
-            .flatten()
-            .map(|m| m.to_string())
-            .unwrap_or_else(|| "00:00:00:00:00:00".to_string());
-        
-        let hw_id = format!("{}-{}-{}", 
-            cpu_info.chars().take(8).collect::<String>(),
-            mac_address.replace(":", ""),
-            sys.total_memory()
-        );
-        
-        use sha2::{Sha256, Digest};
-        let mut hasher = Sha256::new();
-        hasher.update(hw_id.as_bytes());
-        let result = hasher.finalize();
-        
-        Ok(format!("{:X}", result))
+        Ok(crate::hardware_fingerprint::summary_hash(&self.get_hardware_fingerprint()))
+    }
+
+    /// Compare this machine's current fingerprint against a `reference`
+    /// recorded at activation time, accepting it when at least
+    /// `config.match_threshold` components still agree.
+    pub fn verify_hardware_fingerprint(
+        &self,
+        reference: &crate::hardware_fingerprint::HardwareFingerprint,
+        config: &crate::hardware_fingerprint::FingerprintConfig,
+    ) -> crate::hardware_fingerprint::FingerprintMatch {
+        crate::hardware_fingerprint::verify_fingerprint(reference, &self.get_hardware_fingerprint(), config)
     }
     
     pub fn get_feature_value(&self, feature: &str) -> Result<String, String> {
@@ -205,4 +203,29 @@ impl TurboActivate {
             tier: self.get_feature_value("tier").unwrap_or_else(|_| "basic".to_string()),
         }
     }
+
+    /// Verify a self-contained signed license blob without contacting the
+    /// activation server at all. Used when the machine has no network
+    /// access, or to pre-validate a blob before attempting a server
+    /// activation.
+    pub fn verify_offline_license(
+        &self,
+        blob: &crate::offline_license::SignedLicense,
+    ) -> Result<LicenseStatus, crate::offline_license::OfflineLicenseError> {
+        let hardware_id = self.get_hardware_id().unwrap_or_default();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        let claims = crate::offline_license::verify_offline_license(blob, now, &hardware_id)?;
+
+        Ok(LicenseStatus {
+            activated: true,
+            genuine: true,
+            trial: false,
+            hardware_id,
+            tier: claims.tier,
+        })
+    }
 }
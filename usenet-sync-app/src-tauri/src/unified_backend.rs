@@ -1,24 +1,162 @@
 // Unified Backend Integration Module
-// Handles communication with the new unified Python backend
+// Handles communication with the long-lived unified Python backend process.
+// All I/O runs on the tokio runtime so a slow command never blocks the
+// executor the way a blocking `std::process::Command::output()` call would.
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::process::Command;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{broadcast, oneshot, Mutex, Notify};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UnifiedCommand {
+    pub id: u64,
     pub command: String,
     pub args: serde_json::Value,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnifiedResponse {
+    pub id: u64,
     pub success: bool,
     pub data: Option<serde_json::Value>,
     pub error: Option<String>,
 }
 
+/// An unsolicited frame the backend pushes outside the request/response
+/// cycle — a log line as it's written, or progress on a long-running
+/// upload/download. Distinguished from [`UnifiedResponse`] by shape: it has
+/// no `id`/`success`, so a line is tried as a response first and falls back
+/// to this.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendEvent {
+    pub event: String,
+    pub data: serde_json::Value,
+}
+
+/// How many unconsumed events to buffer per lagging subscriber before older
+/// ones are dropped (each stream subscriber gets its own lag counter).
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Default time to wait for a backend response before treating the call as
+/// timed out.
+const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time to wait for the backend to answer the initial handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Protocol version this build of the Rust bridge speaks. Bump whenever a
+/// request field is added that older backends wouldn't understand, and gate
+/// that field behind [`require_min_version`].
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// What the backend told us about itself during the handshake: the protocol
+/// version it negotiated down to, the set of command names it accepts, and
+/// its self-reported version/feature metadata.
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    pub protocol_version: u32,
+    pub protocol_version_minor: u32,
+    pub commands: HashSet<String>,
+    pub server_version: Option<String>,
+    pub feature_flags: Vec<String>,
+}
+
+/// Human-facing summary of [`Capabilities`], returned to the UI by
+/// `get_backend_version` so it can gate functionality when the bundled and
+/// system Python backends diverge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendVersion {
+    /// Human-readable backend version string (e.g. `"2.4.1"`), if the
+    /// backend's handshake response included one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_version: Option<String>,
+    /// `(major, minor)` protocol version negotiated during the handshake.
+    pub protocol_version: (u32, u32),
+    /// Feature identifiers the backend advertises (e.g. `"resumable_upload"`,
+    /// `"webdav"`, `"oidc"`), distinct from the raw command-name allowlist
+    /// used by [`execute_unified_command_cancellable`].
+    pub capabilities: Vec<String>,
+}
+
+/// Errors specific to the backend bridge protocol, as opposed to a plain
+/// I/O or (de)serialization failure. Kept distinct from `String` so callers
+/// can tell "the backend doesn't support this" apart from "the backend is
+/// unreachable" if they want to branch on it; stringified at the
+/// `execute_unified_command*` boundary like every other error in this
+/// module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackendError {
+    /// The backend never completed (or rejected) the protocol handshake, so
+    /// its capabilities are unknown.
+    NotNegotiated,
+    /// The negotiated backend does not advertise this command.
+    UnsupportedCommand { command: String, protocol_version: u32 },
+    /// The negotiated protocol version is too old for a request field the
+    /// caller wants to send.
+    VersionTooOld { have: u32, need: u32 },
+    /// The negotiated backend does not advertise this feature capability
+    /// (e.g. `"resumable_upload"`), as opposed to not recognizing a command
+    /// name at all.
+    UnsupportedCapability { capability: String },
+}
+
+impl std::fmt::Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::NotNegotiated => {
+                write!(f, "backend protocol version has not been negotiated yet")
+            }
+            BackendError::UnsupportedCommand { command, protocol_version } => write!(
+                f,
+                "backend (protocol v{}) does not support command '{}'",
+                protocol_version, command
+            ),
+            BackendError::VersionTooOld { have, need } => write!(
+                f,
+                "backend protocol v{} is older than the v{} required for this request",
+                have, need
+            ),
+            BackendError::UnsupportedCapability { capability } => write!(
+                f,
+                "unsupported by this backend: '{}'",
+                capability
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+/// A cooperative cancellation handle for an in-flight `execute_unified_command`
+/// call. Cancelling does not tear down the shared backend process — it only
+/// abandons this caller's wait, so other pipelined requests are unaffected.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<Notify>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self(Arc::new(Notify::new()))
+    }
+
+    pub fn cancel(&self) {
+        self.0.notify_waiters();
+    }
+
+    async fn cancelled(&self) {
+        self.0.notified().await;
+    }
+}
+
 /// Get the path to the unified backend
 pub fn get_unified_backend_path() -> PathBuf {
     let workspace = get_workspace_dir();
@@ -61,39 +199,415 @@ fn get_python_command() -> &'static str {
     }
 }
 
-/// Execute a command on the unified backend
-pub fn execute_unified_command(command: &str, args: serde_json::Value) -> Result<UnifiedResponse, String> {
-    // Create command structure
+/// A running backend child process plus its write half, kept warm across calls.
+struct BackendProcess {
+    child: Child,
+    stdin: ChildStdin,
+}
+
+/// Supervises a single long-lived backend process, demultiplexing pipelined
+/// `UnifiedCommand`/`UnifiedResponse` pairs by id over its stdin/stdout.
+struct BackendSupervisor {
+    process: Mutex<Option<BackendProcess>>,
+    pending: Mutex<HashMap<u64, oneshot::Sender<UnifiedResponse>>>,
+    next_id: AtomicU64,
+    /// Set once the handshake with the current process completes. Cleared
+    /// whenever the process is (re)spawned, since a new process may be a
+    /// different backend version.
+    capabilities: Mutex<Option<Capabilities>>,
+    /// Fan-out for unsolicited `BackendEvent` frames (log lines, transfer
+    /// progress). Subscribers that fall behind just miss old events rather
+    /// than blocking the reader task.
+    events: broadcast::Sender<BackendEvent>,
+}
+
+static SUPERVISOR: Lazy<BackendSupervisor> = Lazy::new(|| BackendSupervisor {
+    process: Mutex::new(None),
+    pending: Mutex::new(HashMap::new()),
+    next_id: AtomicU64::new(1),
+    capabilities: Mutex::new(None),
+    events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+});
+
+impl BackendSupervisor {
+    /// Make sure a live backend process is running, respawning it if the
+    /// previous one died, and that it has completed the protocol handshake.
+    /// Does not replay any in-flight commands; callers that were waiting on
+    /// the dead process are failed by the reader task.
+    async fn ensure_running(&self) -> Result<(), String> {
+        let mut guard = self.process.lock().await;
+
+        let needs_spawn = match guard.as_mut() {
+            None => true,
+            Some(proc) => matches!(proc.child.try_wait(), Ok(Some(_))),
+        };
+
+        if needs_spawn {
+            *guard = Some(spawn_backend_with_backoff().await?);
+            *self.capabilities.lock().await = None;
+            drop(guard);
+            self.handshake().await;
+        }
+
+        Ok(())
+    }
+
+    /// Kill the current backend process, if any, so the next call to
+    /// `ensure_running` respawns a fresh one. Used by `restart_backend` when
+    /// the GUI user explicitly asks to restart services.
+    async fn kill_current(&self) {
+        if let Some(mut proc) = self.process.lock().await.take() {
+            let _ = proc.child.kill().await;
+        }
+        *self.capabilities.lock().await = None;
+    }
+
+    /// Negotiate the protocol version and capability list with a freshly
+    /// spawned backend. Best-effort: a backend too old to understand the
+    /// handshake simply leaves `capabilities` unset, and commands are sent
+    /// without a capability check (see `execute_unified_command_cancellable`).
+    async fn handshake(&self) {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let cmd = UnifiedCommand {
+            id,
+            command: "handshake".to_string(),
+            args: json!({ "protocol_version": PROTOCOL_VERSION }),
+        };
+        let mut line = match serde_json::to_string(&cmd) {
+            Ok(l) => l,
+            Err(_) => return,
+        };
+        line.push('\n');
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, tx);
+
+        if self.write_line(&line).await.is_err() {
+            self.pending.lock().await.remove(&id);
+            return;
+        }
+
+        let response = match tokio::time::timeout(HANDSHAKE_TIMEOUT, rx).await {
+            Ok(Ok(response)) => response,
+            _ => {
+                self.pending.lock().await.remove(&id);
+                return;
+            }
+        };
+
+        if !response.success {
+            return;
+        }
+
+        let data = match response.data {
+            Some(d) => d,
+            None => return,
+        };
+        let protocol_version = match data.get("protocol_version").and_then(|v| v.as_u64()) {
+            Some(v) => v as u32,
+            None => return,
+        };
+        let commands: HashSet<String> = match data.get("capabilities").and_then(|v| v.as_array()) {
+            Some(arr) => arr.iter().filter_map(|c| c.as_str().map(str::to_string)).collect(),
+            None => return,
+        };
+        let protocol_version_minor = data
+            .get("protocol_version_minor")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u32;
+        let server_version = data
+            .get("server_version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        let feature_flags: Vec<String> = data
+            .get("feature_flags")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|c| c.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+
+        *self.capabilities.lock().await = Some(Capabilities {
+            protocol_version,
+            protocol_version_minor,
+            commands,
+            server_version,
+            feature_flags,
+        });
+    }
+
+    async fn write_line(&self, line: &str) -> Result<(), String> {
+        let mut guard = self.process.lock().await;
+        let proc = guard
+            .as_mut()
+            .ok_or_else(|| "Backend process not running".to_string())?;
+        proc.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to backend: {}", e))?;
+        proc.stdin
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to flush backend stdin: {}", e))
+    }
+}
+
+/// Number of times to retry spawning the backend before giving up.
+const SPAWN_RETRIES: u32 = 3;
+
+/// Base delay between spawn attempts; doubled on each retry.
+const SPAWN_BACKOFF_BASE: Duration = Duration::from_millis(200);
+
+/// Retry spawning the backend with exponential backoff. A spawn can fail
+/// transiently right after a crash (e.g. a lock file or socket the old
+/// process held hasn't been released yet), so absorb a few attempts here
+/// rather than surfacing the first failure to every caller.
+async fn spawn_backend_with_backoff() -> Result<BackendProcess, String> {
+    let mut last_err = String::new();
+    for attempt in 0..SPAWN_RETRIES {
+        match spawn_backend().await {
+            Ok(proc) => return Ok(proc),
+            Err(e) => {
+                last_err = e;
+                tokio::time::sleep(SPAWN_BACKOFF_BASE * 2u32.pow(attempt)).await;
+            }
+        }
+    }
+    Err(format!(
+        "Failed to spawn backend after {} attempts: {}",
+        SPAWN_RETRIES, last_err
+    ))
+}
+
+/// Spawn the backend process and start the reader task that demultiplexes
+/// its stdout lines to whichever caller is waiting on that response id.
+async fn spawn_backend() -> Result<BackendProcess, String> {
+    let mut child = Command::new(get_python_command())
+        .arg(get_unified_backend_path())
+        .arg("--mode")
+        .arg("daemon")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to spawn backend: {}", e))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Backend stdout was not captured".to_string())?;
+    let stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "Backend stdin was not captured".to_string())?;
+
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(stdout).lines();
+        loop {
+            match reader.next_line().await {
+                Ok(Some(line)) => {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    // A reply to one of our requests has an `id`/`success`;
+                    // anything else is an unsolicited event (log line,
+                    // transfer progress) pushed by the backend on its own.
+                    if let Ok(response) = serde_json::from_str::<UnifiedResponse>(&line) {
+                        if let Some(sender) = SUPERVISOR.pending.lock().await.remove(&response.id) {
+                            let _ = sender.send(response);
+                        }
+                    } else if let Ok(event) = serde_json::from_str::<BackendEvent>(&line) {
+                        // No receivers yet (e.g. no log viewer open) is fine.
+                        let _ = SUPERVISOR.events.send(event);
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        // The backend exited (or its pipe closed); fail every request still
+        // waiting on a response rather than leaving callers hanging.
+        for (_, sender) in SUPERVISOR.pending.lock().await.drain() {
+            let _ = sender.send(UnifiedResponse {
+                id: 0,
+                success: false,
+                data: None,
+                error: Some("Backend process exited".to_string()),
+            });
+        }
+    });
+
+    Ok(BackendProcess { child, stdin })
+}
+
+/// Execute a command on the unified backend, reusing the long-lived backend
+/// process instead of spawning a fresh interpreter for every call. `args` is
+/// always serialized as the JSON envelope's data, never spliced into a shell
+/// or interpreter source string, so callers can pass arbitrary user-supplied
+/// content (passwords, file paths, imported blobs) without it being treated
+/// as code. Uses the default call timeout and is not cancellable; see
+/// [`execute_unified_command_cancellable`] for long-running operations the
+/// GUI may want to abort.
+pub async fn execute_unified_command(
+    command: &str,
+    args: serde_json::Value,
+) -> Result<UnifiedResponse, String> {
+    execute_unified_command_cancellable(command, args, None, DEFAULT_CALL_TIMEOUT).await
+}
+
+/// Execute a command on the unified backend with an explicit timeout and an
+/// optional [`CancelToken`] a caller can trigger (e.g. when the GUI user
+/// navigates away mid-transfer) to stop waiting on the response.
+pub async fn execute_unified_command_cancellable(
+    command: &str,
+    args: serde_json::Value,
+    cancel: Option<CancelToken>,
+    call_timeout: Duration,
+) -> Result<UnifiedResponse, String> {
+    SUPERVISOR.ensure_running().await?;
+
+    // Reject commands the negotiated backend doesn't advertise up front,
+    // instead of sending them and getting back a confusing parse error.
+    // A backend that never completed the handshake is given the benefit of
+    // the doubt (capabilities == None) so older backends keep working.
+    if let Some(caps) = SUPERVISOR.capabilities.lock().await.as_ref() {
+        if !caps.commands.contains(command) {
+            return Err(BackendError::UnsupportedCommand {
+                command: command.to_string(),
+                protocol_version: caps.protocol_version,
+            }
+            .to_string());
+        }
+    }
+
+    let id = SUPERVISOR.next_id.fetch_add(1, Ordering::SeqCst);
     let cmd_data = UnifiedCommand {
+        id,
         command: command.to_string(),
         args,
     };
-    
-    // Serialize to JSON
-    let cmd_json = serde_json::to_string(&cmd_data)
+
+    let mut line = serde_json::to_string(&cmd_data)
         .map_err(|e| format!("Failed to serialize command: {}", e))?;
-    
-    // Execute Python backend
-    let output = Command::new(get_python_command())
-        .arg(get_unified_backend_path())
-        .arg("--mode")
-        .arg("command")
-        .arg("--command")
-        .arg(&cmd_json)
-        .output()
-        .map_err(|e| format!("Failed to execute backend: {}", e))?;
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Backend error: {}", stderr));
+    line.push('\n');
+
+    let (tx, rx) = oneshot::channel();
+    SUPERVISOR.pending.lock().await.insert(id, tx);
+
+    if let Err(e) = SUPERVISOR.write_line(&line).await {
+        SUPERVISOR.pending.lock().await.remove(&id);
+        return Err(e);
+    }
+
+    let result = if let Some(cancel) = cancel {
+        tokio::select! {
+            resp = rx => resp.map_err(|_| "Backend connection closed before responding".to_string()),
+            _ = cancel.cancelled() => Err("Command cancelled".to_string()),
+            _ = tokio::time::sleep(call_timeout) => Err(format!("Command timed out after {:?}", call_timeout)),
+        }
+    } else {
+        tokio::select! {
+            resp = rx => resp.map_err(|_| "Backend connection closed before responding".to_string()),
+            _ = tokio::time::sleep(call_timeout) => Err(format!("Command timed out after {:?}", call_timeout)),
+        }
+    };
+
+    if result.is_err() {
+        // The response may still arrive after we stop waiting; drop the
+        // now-orphaned sender so the reader task doesn't hold it forever.
+        SUPERVISOR.pending.lock().await.remove(&id);
+    }
+
+    result
+}
+
+/// Subscribe to the backend's unsolicited event stream (log lines, transfer
+/// progress). Each subscriber gets its own receiver and its own lag
+/// counter, so a slow consumer only drops events for itself.
+pub fn subscribe_events() -> broadcast::Receiver<BackendEvent> {
+    SUPERVISOR.events.subscribe()
+}
+
+/// The protocol version negotiated with the current backend process, or
+/// `None` if the handshake hasn't completed (e.g. the backend hasn't been
+/// spawned yet, or is too old to understand it).
+pub async fn negotiated_version() -> Option<u32> {
+    SUPERVISOR
+        .capabilities
+        .lock()
+        .await
+        .as_ref()
+        .map(|caps| caps.protocol_version)
+}
+
+/// Gate a request field that only newer backends understand behind the
+/// negotiated protocol version, so it's never sent to a backend too old to
+/// parse it.
+pub async fn require_min_version(min_version: u32) -> Result<(), String> {
+    match negotiated_version().await {
+        Some(have) if have >= min_version => Ok(()),
+        Some(have) => Err(BackendError::VersionTooOld { have, need: min_version }.to_string()),
+        None => Err(BackendError::NotNegotiated.to_string()),
+    }
+}
+
+/// The full version/capability handshake result for the current backend
+/// process, or `None` if the handshake hasn't completed.
+pub async fn backend_version() -> Option<BackendVersion> {
+    SUPERVISOR.capabilities.lock().await.as_ref().map(|caps| BackendVersion {
+        server_version: caps.server_version.clone(),
+        protocol_version: (caps.protocol_version, caps.protocol_version_minor),
+        capabilities: caps.feature_flags.clone(),
+    })
+}
+
+/// Gate a call on the negotiated backend advertising `capability` (e.g.
+/// `"resumable_upload"`, `"webdav"`, `"oidc"`), failing fast with a clear
+/// "unsupported by this backend" error instead of sending a request the
+/// backend has no idea how to honor.
+pub async fn require_capability(capability: &str) -> Result<(), String> {
+    match SUPERVISOR.capabilities.lock().await.as_ref() {
+        Some(caps) if caps.feature_flags.iter().any(|flag| flag == capability) => Ok(()),
+        Some(_) => Err(BackendError::UnsupportedCapability {
+            capability: capability.to_string(),
+        }
+        .to_string()),
+        None => Err(BackendError::NotNegotiated.to_string()),
+    }
+}
+
+/// Ping the backend and confirm it answers within a short timeout. Used
+/// after [`restart_backend`] to make sure the new process is actually
+/// accepting commands rather than just alive-but-wedged.
+pub async fn health_check() -> bool {
+    matches!(
+        execute_unified_command_cancellable("ping", json!({}), None, Duration::from_secs(5)).await,
+        Ok(response) if response.success
+    )
+}
+
+/// Force the supervised backend process to restart (e.g. the GUI user hit
+/// "Restart services"), then confirm the replacement is healthy before
+/// returning so callers don't report success against a process that never
+/// came up.
+pub async fn restart_backend() -> Result<(), String> {
+    SUPERVISOR.kill_current().await;
+    SUPERVISOR.ensure_running().await?;
+
+    if !health_check().await {
+        return Err("Backend did not respond to health check after restart".to_string());
+    }
+
+    Ok(())
+}
+
+/// If `input` is a valid share code, decode it back to its raw id (hex
+/// encoded for transport); otherwise pass it through unchanged so a raw
+/// share id still works.
+fn normalize_share_id(input: &str) -> String {
+    match crate::share_code::decode_share_code(input) {
+        Ok(bytes) => hex::encode(bytes),
+        Err(_) => input.to_string(),
     }
-    
-    // Parse response
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let response: UnifiedResponse = serde_json::from_str(&stdout)
-        .map_err(|e| format!("Failed to parse response: {} - Output: {}", e, stdout))?;
-    
-    Ok(response)
 }
 
 /// Helper to convert old CLI arguments to unified format
@@ -117,57 +631,60 @@ pub fn convert_cli_args_to_unified(command: &str, args: Vec<String>) -> serde_js
             "priority": args.get(1).unwrap_or(&String::from("normal"))
         }),
         "download" => json!({
-            "share_id": args.get(0).unwrap_or(&String::new()),
+            // Accept either a raw share id or a human-typed share code.
+            "share_id": args.get(0).map(|s| normalize_share_id(s)).unwrap_or_default(),
             "output_path": args.get(1).unwrap_or(&String::from("./downloads"))
         }),
         _ => json!({
             "args": args
-        })
+        }),
     }
 }
 
 /// Wrapper to use unified backend with fallback to old CLI
-pub fn execute_backend_command(command: &str, args: Vec<String>) -> Result<serde_json::Value, String> {
+pub async fn execute_backend_command(
+    command: &str,
+    args: Vec<String>,
+) -> Result<serde_json::Value, String> {
     if unified_backend_exists() {
-        // Use unified backend
         let unified_args = convert_cli_args_to_unified(command, args);
-        let response = execute_unified_command(command, unified_args)?;
-        
+        let response = crate::operation_log::execute_offline_first(command, unified_args).await?;
+
         if response.success {
             Ok(response.data.unwrap_or(json!({})))
         } else {
             Err(response.error.unwrap_or_else(|| "Unknown error".to_string()))
         }
     } else {
-        // Fallback to old CLI
-        execute_old_cli(command, args)
+        execute_old_cli(command, args).await
     }
 }
 
 /// Execute old CLI command (fallback)
-fn execute_old_cli(command: &str, args: Vec<String>) -> Result<serde_json::Value, String> {
+async fn execute_old_cli(command: &str, args: Vec<String>) -> Result<serde_json::Value, String> {
     let mut cmd = Command::new(get_python_command());
     cmd.arg(get_workspace_dir().join("src").join("cli.py"));
     cmd.arg(command);
-    
+
     for arg in args {
         cmd.arg(arg);
     }
-    
-    let output = cmd.output()
+
+    let output = cmd
+        .output()
+        .await
         .map_err(|e| format!("Failed to execute CLI: {}", e))?;
-    
+
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("CLI error: {}", stderr));
     }
-    
+
     let stdout = String::from_utf8_lossy(&output.stdout);
-    
-    // Try to parse as JSON, otherwise return as string
+
     if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout) {
         Ok(json)
     } else {
         Ok(json!({ "output": stdout.to_string() }))
     }
-}
\ No newline at end of file
+}
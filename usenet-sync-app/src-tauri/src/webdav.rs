@@ -0,0 +1,357 @@
+// Read-only WebDAV gateway for a published share.
+//
+// Exposes a share's already-indexed `FileNode` tree as a minimal read-only
+// WebDAV filesystem: PROPFIND for directory listings, GET/HEAD (with byte
+// range support) for streaming file contents. Binds a plain HTTP/1.1
+// listener and hand-parses requests, the same way `metrics.rs` serves its
+// scrape endpoint, rather than pulling in a full webdav-handler/axum stack
+// for a handful of verbs. File contents are fetched on demand through the
+// backend's segment retrieval command instead of requiring a full local
+// download first.
+
+use std::sync::Arc;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Notify, Semaphore};
+
+use crate::FileNode;
+
+/// A running WebDAV gateway for one share, tracked in `AppState` so
+/// `stop_webdav_gateway` can shut it down cleanly.
+pub struct WebdavGatewayHandle {
+    pub share_id: String,
+    pub bind_addr: String,
+    shutdown: Arc<Notify>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl WebdavGatewayHandle {
+    /// Signal the accept loop to stop and wait for it to exit. In-flight
+    /// client connections are left to finish on their own.
+    pub async fn shutdown(self) {
+        self.shutdown.notify_one();
+        let _ = self.task.await;
+    }
+}
+
+/// Start a read-only WebDAV gateway for `share_id`'s `root` file tree,
+/// bound to `bind_addr` (e.g. `"127.0.0.1:9898"`), accepting at most
+/// `max_connections` concurrent clients. When `password` is `Some`, every
+/// request must present it as the password half of HTTP Basic Auth (any
+/// username is accepted); `bind_addr` is caller-supplied and not limited
+/// to loopback, so an unprotected gateway would otherwise serve a
+/// password-protected share's contents to anyone who can reach it.
+pub async fn start(
+    share_id: String,
+    bind_addr: String,
+    root: FileNode,
+    max_connections: usize,
+    password: Option<String>,
+) -> Result<WebdavGatewayHandle, String> {
+    let listener = TcpListener::bind(&bind_addr)
+        .await
+        .map_err(|e| format!("failed to bind {}: {}", bind_addr, e))?;
+
+    let shutdown = Arc::new(Notify::new());
+    let task_shutdown = shutdown.clone();
+    let root = Arc::new(root);
+    let password = Arc::new(password);
+    let connections = Arc::new(Semaphore::new(max_connections.max(1)));
+    let task_share_id = share_id.clone();
+
+    let task = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = task_shutdown.notified() => break,
+                accepted = listener.accept() => {
+                    let (socket, _) = match accepted {
+                        Ok(conn) => conn,
+                        Err(_) => continue,
+                    };
+                    let Ok(permit) = connections.clone().try_acquire_owned() else {
+                        // At the connection cap for this license tier; drop
+                        // the client rather than queuing it indefinitely.
+                        continue;
+                    };
+                    let root = root.clone();
+                    let share_id = task_share_id.clone();
+                    let password = password.clone();
+                    tokio::spawn(async move {
+                        let _permit = permit;
+                        handle_connection(socket, root, share_id, password).await;
+                    });
+                }
+            }
+        }
+    });
+
+    Ok(WebdavGatewayHandle {
+        share_id,
+        bind_addr,
+        shutdown,
+        task,
+    })
+}
+
+/// Compare two passwords in constant time, so a failed guess can't be
+/// distinguished by how long the comparison took.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Decode a `Basic` `Authorization` header value and return its password
+/// half (the username is accepted unconditionally; the share password is
+/// what actually gates access here).
+fn basic_auth_password(header_value: &str) -> Option<String> {
+    let encoded = header_value.trim().strip_prefix("Basic ")?;
+    let decoded = base64::decode(encoded).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (_user, password) = decoded.split_once(':')?;
+    Some(password.to_string())
+}
+
+async fn handle_connection(
+    mut socket: TcpStream,
+    root: Arc<FileNode>,
+    share_id: String,
+    password: Arc<Option<String>>,
+) {
+    let mut buf = vec![0u8; 8192];
+    let n = match socket.read(&mut buf).await {
+        Ok(n) if n > 0 => n,
+        _ => return,
+    };
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let mut lines = request.lines();
+    let request_line = match lines.next() {
+        Some(line) => line,
+        None => return,
+    };
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let raw_path = parts.next().unwrap_or("/");
+    let path = percent_decode(raw_path);
+
+    let mut range_header: Option<String> = None;
+    let mut authorization_header: Option<String> = None;
+    for line in lines {
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("range") {
+                range_header = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorization_header = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    if let Some(expected) = password.as_ref() {
+        let presented = authorization_header.as_deref().and_then(basic_auth_password);
+        if !presented.is_some_and(|presented| constant_time_eq(&presented, expected)) {
+            let _ = socket.write_all(&unauthorized_response()).await;
+            return;
+        }
+    }
+
+    let response = match method {
+        "OPTIONS" => options_response(),
+        "PROPFIND" => propfind_response(&root, &path),
+        "GET" => get_response(&root, &path, range_header.as_deref(), &share_id, false).await,
+        "HEAD" => get_response(&root, &path, range_header.as_deref(), &share_id, true).await,
+        _ => not_allowed_response(),
+    };
+
+    let _ = socket.write_all(&response).await;
+}
+
+/// Find the node at `path` (slash-separated, relative to the share root) by
+/// walking `FileNode::children` by name.
+fn find_node<'a>(root: &'a FileNode, path: &str) -> Option<&'a FileNode> {
+    let trimmed = path.trim_matches('/');
+    if trimmed.is_empty() {
+        return Some(root);
+    }
+
+    let mut current = root;
+    for segment in trimmed.split('/') {
+        current = current.children.as_ref()?.iter().find(|c| c.name == segment)?;
+    }
+    Some(current)
+}
+
+fn propfind_response(root: &FileNode, path: &str) -> Vec<u8> {
+    let node = match find_node(root, path) {
+        Some(node) => node,
+        None => return not_found_response(),
+    };
+
+    let mut body = String::from(r#"<?xml version="1.0" encoding="utf-8"?><D:multistatus xmlns:D="DAV:">"#);
+    body.push_str(&propfind_entry(node, path));
+    if let Some(children) = &node.children {
+        let base = path.trim_end_matches('/');
+        for child in children {
+            body.push_str(&propfind_entry(child, &format!("{}/{}", base, child.name)));
+        }
+    }
+    body.push_str("</D:multistatus>");
+
+    http_response(207, "Multi-Status", "application/xml; charset=utf-8", body.as_bytes())
+}
+
+fn propfind_entry(node: &FileNode, href: &str) -> String {
+    let resourcetype = if node.node_type == "folder" {
+        "<D:resourcetype><D:collection/></D:resourcetype>"
+    } else {
+        "<D:resourcetype/>"
+    };
+    format!(
+        "<D:response><D:href>{}</D:href><D:propstat><D:prop>{}<D:getcontentlength>{}</D:getcontentlength>\
+<D:getlastmodified>{}</D:getlastmodified></D:prop><D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>",
+        xml_escape(href),
+        resourcetype,
+        node.size,
+        xml_escape(&node.modified_at),
+    )
+}
+
+async fn get_response(
+    root: &FileNode,
+    path: &str,
+    range: Option<&str>,
+    share_id: &str,
+    head_only: bool,
+) -> Vec<u8> {
+    let node = match find_node(root, path) {
+        Some(node) if node.node_type == "file" => node,
+        Some(_) => return not_allowed_response(),
+        None => return not_found_response(),
+    };
+
+    let total = node.size;
+    let is_partial = range.is_some();
+    let (start, end) = match range.and_then(parse_range_header) {
+        Some((start, Some(end))) => (start, end.min(total.saturating_sub(1))),
+        Some((start, None)) => (start, total.saturating_sub(1)),
+        None => (0, total.saturating_sub(1)),
+    };
+    let length = end.saturating_sub(start) + 1;
+
+    if head_only {
+        return range_response(is_partial, start, end, total, length, &[]);
+    }
+
+    let args = serde_json::json!({
+        "share_id": share_id,
+        "path": node.path,
+        "offset": start,
+        "length": length,
+    });
+
+    let bytes = match crate::unified_backend::execute_unified_command("read_share_file_range", args).await {
+        Ok(response) if response.success => response
+            .data
+            .and_then(|d| d.get("data").and_then(|v| v.as_str()).map(str::to_string))
+            .and_then(|encoded| base64::decode(encoded).ok())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    range_response(is_partial, start, end, total, length, &bytes)
+}
+
+fn parse_range_header(value: &str) -> Option<(u64, Option<u64>)> {
+    let spec = value.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+    let start = start.parse::<u64>().ok()?;
+    let end = if end.is_empty() { None } else { Some(end.parse::<u64>().ok()?) };
+    Some((start, end))
+}
+
+fn range_response(is_partial: bool, start: u64, end: u64, total: u64, length: u64, body: &[u8]) -> Vec<u8> {
+    if is_partial && total > 0 {
+        let mut response = format!(
+            "HTTP/1.1 206 Partial Content\r\nContent-Type: application/octet-stream\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\nAccept-Ranges: bytes\r\nConnection: close\r\n\r\n",
+            start, end, total, length
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    } else {
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nAccept-Ranges: bytes\r\nConnection: close\r\n\r\n",
+            length
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+}
+
+fn options_response() -> Vec<u8> {
+    b"HTTP/1.1 200 OK\r\nDAV: 1\r\nAllow: OPTIONS, GET, HEAD, PROPFIND\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+}
+
+fn not_found_response() -> Vec<u8> {
+    http_response(404, "Not Found", "text/plain", b"Not Found")
+}
+
+fn not_allowed_response() -> Vec<u8> {
+    http_response(405, "Method Not Allowed", "text/plain", b"Method Not Allowed")
+}
+
+fn unauthorized_response() -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"{}\"\r\nContent-Type: text/plain\r\nContent-Length: 12\r\nConnection: close\r\n\r\n",
+        "WebDAV share"
+    )
+    .into_bytes();
+    response.extend_from_slice(b"Unauthorized");
+    response
+}
+
+fn http_response(status: u16, reason: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        content_type,
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
+    response
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(&input[i + 1..i + 3], 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn xml_escape(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}